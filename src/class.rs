@@ -1,8 +1,9 @@
 use crate::{Context, GlobalValue, value::Value};
 
 #[derive(Copy, Clone)]
-pub struct CallOptions {
+pub struct CallOptions<'a> {
     pub constructor: bool,
+    pub new_target: Option<&'a Value<'a>>,
 }
 
 pub trait GCMarker {
@@ -13,6 +14,11 @@ pub trait GCMarker {
 pub trait Class: Send + 'static {
     const NAME: &'static str;
 
+    // reported through Symbol.toStringTag when differs from NAME and by class_name_of, class id is still keyed by type
+    fn class_name(&self) -> &str {
+        Self::NAME
+    }
+
     fn call<'rt>(
         &self,
         ctx: &Context<'rt>,
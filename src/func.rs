@@ -51,7 +51,41 @@ impl<'rt> NativeFunctionExt<'rt> for Context<'rt> {
     where
         F: for<'r> Fn(&Context<'r>, &Value, &Value, &[Value], CallOptions) -> Result<Value<'r>, Value<'r>> + Send + 'static,
     {
-        let func = NativeFunction::new(func);
-        self.define_property_value_str(obj, &name, self.new_object_class(func, None)?, Default::default())
+        let func = self.new_object_class(NativeFunction::new(func), None)?;
+        self.set_function_name(&func, name)?;
+        self.define_property_value_str(obj, &name, func, Default::default())
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct JsFunction<'rt> {
+    func: Value<'rt>,
+}
+
+impl<'rt> JsFunction<'rt> {
+    pub fn as_value(&self) -> &Value<'rt> {
+        &self.func
+    }
+
+    pub fn into_value(self) -> Value<'rt> {
+        self.func
+    }
+
+    pub fn call(&self, ctx: &Context<'rt>, this: &Value, args: &[Value]) -> Result<Value<'rt>, Value<'rt>> {
+        ctx.call(&self.func, this, args)
+    }
+
+    pub fn call0(&self, ctx: &Context<'rt>) -> Result<Value<'rt>, Value<'rt>> {
+        ctx.call(&self.func, &Value::Undefined, &[])
+    }
+}
+
+impl<'rt> Value<'rt> {
+    pub fn as_function(&self, ctx: &Context<'rt>) -> Option<JsFunction<'rt>> {
+        if ctx.is_function(self) {
+            Some(JsFunction { func: self.clone() })
+        } else {
+            None
+        }
     }
 }
@@ -10,15 +10,18 @@ mod utils;
 mod value;
 
 use std::{
-    any::TypeId,
-    cell::RefCell,
-    collections::{HashMap, hash_map::Entry},
+    any::{Any, TypeId},
+    cell::{Cell, OnceCell, Ref, RefCell},
+    collections::{HashMap, HashSet, hash_map::Entry},
     ffi::{CStr, CString},
     fmt::{Debug, Display, Formatter},
     mem::ManuallyDrop,
     num::NonZeroUsize,
     ops::Deref,
+    panic::AssertUnwindSafe,
     ptr::NonNull,
+    rc::Rc,
+    time::{Duration, Instant},
 };
 
 use bitflags::bitflags;
@@ -26,35 +29,46 @@ use rquickjs_sys::{
     JS_AddIntrinsicBaseObjects, JS_AddIntrinsicBigInt, JS_AddIntrinsicDOMException, JS_AddIntrinsicDate, JS_AddIntrinsicEval,
     JS_AddIntrinsicJSON, JS_AddIntrinsicMapSet, JS_AddIntrinsicPromise, JS_AddIntrinsicProxy, JS_AddIntrinsicRegExp,
     JS_AddIntrinsicRegExpCompiler, JS_AddIntrinsicTypedArrays, JS_AddIntrinsicWeakRef, JS_AtomToString, JS_AtomToValue, JS_Call,
-    JS_CallConstructor2, JS_ClearUncatchableError, JS_DefineProperty, JS_DefinePropertyGetSet, JS_DefinePropertyValue,
-    JS_DefinePropertyValueStr, JS_DefinePropertyValueUint32, JS_DeleteProperty, JS_DetachArrayBuffer, JS_DetectModule,
-    JS_DupAtom, JS_DupContext, JS_DupValueRT, JS_EnqueueJob, JS_Eval, JS_EvalFunction, JS_EvalThis, JS_ExecutePendingJob,
-    JS_FreeAtomRT, JS_FreeCString, JS_FreeContext, JS_FreePropertyEnum, JS_FreeRuntime, JS_FreeValueRT, JS_FreezeObject,
-    JS_GetArrayBuffer, JS_GetClassID, JS_GetClassProto, JS_GetException, JS_GetFunctionProto, JS_GetGlobalObject, JS_GetLength,
-    JS_GetOpaque, JS_GetOwnProperty, JS_GetOwnPropertyNames, JS_GetProperty, JS_GetPropertyStr, JS_GetPropertyUint32,
-    JS_GetPrototype, JS_GetRuntime, JS_GetRuntimeOpaque, JS_GetTypedArrayBuffer, JS_GetTypedArrayType, JS_GetUint8Array,
-    JS_HasProperty, JS_Invoke, JS_IsArray, JS_IsArrayBuffer, JS_IsConstructor, JS_IsDate, JS_IsEqual, JS_IsError,
-    JS_IsExtensible, JS_IsFunction, JS_IsInstanceOf, JS_IsMap, JS_IsPromise, JS_IsRegExp, JS_IsRegisteredClass, JS_IsSameValue,
+    JS_CallConstructor2, JS_ClearUncatchableError, JS_ComputeMemoryUsage, JS_DefineProperty, JS_DefinePropertyGetSet,
+    JS_DefinePropertyValue, JS_DefinePropertyValueStr, JS_DefinePropertyValueUint32, JS_DeleteProperty, JS_DetachArrayBuffer,
+    JS_DetectModule, JS_DupAtom, JS_DupContext, JS_DupValueRT, JS_EnqueueJob, JS_Eval, JS_EvalFunction, JS_EvalThis,
+    JS_ExecutePendingJob, JS_FreeAtomRT, JS_FreeCString, JS_FreeContext, JS_FreePropertyEnum, JS_FreeRuntime, JS_FreeValueRT,
+    JS_FreezeObject, JS_GetArrayBuffer, JS_GetClassID, JS_GetClassProto, JS_GetException, JS_GetFunctionProto, JS_GetGCThreshold,
+    JS_GetGlobalObject, JS_GetImportMeta, JS_GetLength, JS_GetModuleName, JS_GetModuleNamespace, JS_GetOpaque, JS_GetOwnProperty,
+    JS_GetOwnPropertyNames, JS_GetProperty, JS_GetPropertyStr, JS_GetPropertyUint32, JS_GetPrototype, JS_GetProxyTarget,
+    JS_GetRuntime, JS_GetRuntimeOpaque, JS_GetTypedArrayBuffer, JS_GetTypedArrayType, JS_GetUint8Array, JS_HasProperty,
+    JS_Invoke, JS_IsArray, JS_IsArrayBuffer, JS_IsConstructor, JS_IsDate, JS_IsEqual, JS_IsError, JS_IsExtensible, JS_IsFunction,
+    JS_IsInstanceOf, JS_IsJobPending, JS_IsMap, JS_IsPromise, JS_IsProxy, JS_IsRegExp, JS_IsRegisteredClass, JS_IsSameValue,
     JS_IsSameValueZero, JS_IsStrictEqual, JS_IsUncatchableError, JS_JSONStringify, JS_MarkValue, JS_NewArray, JS_NewArrayBuffer,
     JS_NewArrayBufferCopy, JS_NewAtomLen, JS_NewAtomUInt32, JS_NewBigInt64, JS_NewBigUint64, JS_NewClass, JS_NewClassID,
     JS_NewContext, JS_NewContextRaw, JS_NewDate, JS_NewError, JS_NewFloat64, JS_NewNumber, JS_NewObject, JS_NewObjectClass,
     JS_NewObjectProto, JS_NewObjectProtoClass, JS_NewPromiseCapability, JS_NewStringLen, JS_NewSymbol, JS_NewTypedArray,
     JS_NewUint8Array, JS_NewUint8ArrayCopy, JS_ParseJSON, JS_PreventExtensions, JS_PromiseResult, JS_PromiseState, JS_ReadObject,
-    JS_ResolveModule, JS_RunGC, JS_SealObject, JS_SetClassProto, JS_SetConstructorBit, JS_SetLength, JS_SetMaxStackSize,
-    JS_SetOpaque, JS_SetProperty, JS_SetPropertyInt64, JS_SetPropertyStr, JS_SetPropertyUint32, JS_SetPrototype,
-    JS_SetRuntimeOpaque, JS_SetUncatchableError, JS_Throw, JS_ThrowTypeError, JS_ToBigInt64, JS_ToBool, JS_ToCStringLen2,
-    JS_ToFloat64, JS_ToIndex, JS_ToInt32, JS_ToInt64Ext, JS_ToNumber, JS_ToObject, JS_ToObjectString, JS_ToPropertyKey,
-    JS_ToString, JS_UpdateStackTop, JS_ValueToAtom, JS_WriteObject, js_free,
+    JS_ResolveModule, JS_RunGC, JS_SealObject, JS_SetClassProto, JS_SetConstructorBit, JS_SetGCThreshold,
+    JS_SetHostPromiseRejectionTracker, JS_SetInterruptHandler, JS_SetLength, JS_SetMaxStackSize, JS_SetMemoryLimit,
+    JS_SetModuleLoaderFunc, JS_SetOpaque, JS_SetProperty, JS_SetPropertyInt64, JS_SetPropertyStr, JS_SetPropertyUint32,
+    JS_SetPrototype, JS_SetRuntimeOpaque, JS_SetUncatchableError, JS_Throw, JS_ThrowInternalError, JS_ThrowPlainError,
+    JS_ThrowRangeError, JS_ThrowReferenceError, JS_ThrowSyntaxError, JS_ThrowTypeError, JS_ToBigInt64, JS_ToBool,
+    JS_ToCStringLen2, JS_ToFloat64, JS_ToIndex, JS_ToInt32, JS_ToInt64Ext, JS_ToNumber, JS_ToObject, JS_ToObjectString,
+    JS_ToPropertyKey, JS_ToString, JS_UpdateStackTop, JS_ValueToAtom, JS_WriteObject, js_free, js_free_rt, js_strndup,
 };
 
 use crate::utils::{
     cstr::MaybeTinyCString,
     global::{Global, GlobalHolder},
+    json::strip_json_extensions,
+    module::normalize_module_name,
     ptr::enforce_not_out_of_memory,
     vec::MaybeTinyVec,
 };
 pub use crate::{atom::*, class::*, func::*, prop::*, value::*};
 
+// stack capacity for property names and symbol descriptions passed as &str
+const PROP_NAME_TINY_CAP: usize = 64;
+
+// nesting limit of stringify_to_writer, keeps its recursion well within a thread stack
+const JSON_WRITER_MAX_DEPTH: usize = 128;
+
 #[derive(Debug, Copy, Clone)]
 pub struct InvalidRuntime;
 
@@ -66,6 +80,55 @@ impl Display for InvalidRuntime {
 
 impl std::error::Error for InvalidRuntime {}
 
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct OutstandingHandles {
+    pub values: usize,
+    pub contexts: usize,
+    pub atoms: usize,
+}
+
+impl Display for OutstandingHandles {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "outstanding handles at shutdown: {} values, {} contexts, {} atoms",
+            self.values, self.contexts, self.atoms
+        )
+    }
+}
+
+impl std::error::Error for OutstandingHandles {}
+
+#[derive(Debug, Copy, Clone, Default, PartialEq, Eq)]
+pub struct MemoryUsage {
+    pub malloc_size: i64,
+    pub malloc_limit: i64,
+    pub memory_used_size: i64,
+    pub malloc_count: i64,
+    pub memory_used_count: i64,
+    pub atom_count: i64,
+    pub atom_size: i64,
+    pub str_count: i64,
+    pub str_size: i64,
+    pub obj_count: i64,
+    pub obj_size: i64,
+    pub prop_count: i64,
+    pub prop_size: i64,
+    pub shape_count: i64,
+    pub shape_size: i64,
+    pub js_func_count: i64,
+    pub js_func_size: i64,
+    pub js_func_code_size: i64,
+    pub js_func_pc2line_count: i64,
+    pub js_func_pc2line_size: i64,
+    pub c_func_count: i64,
+    pub array_count: i64,
+    pub fast_array_count: i64,
+    pub fast_array_elements: i64,
+    pub binary_object_count: i64,
+    pub binary_object_size: i64,
+}
+
 #[derive(Clone)]
 pub struct GlobalContext {
     global: Global<NonNull<rquickjs_sys::JSContext>>,
@@ -79,6 +142,7 @@ impl GlobalContext {
                 let ctx = Context {
                     rt,
                     ptr: unsafe { enforce_not_out_of_memory(JS_DupContext(ctx.as_ptr())) },
+                    global: OnceCell::new(),
                 };
 
                 // current thread may change, update stack top
@@ -118,12 +182,39 @@ impl GlobalAtom {
     }
 }
 
+type ModuleResolver = dyn for<'c, 'r> Fn(&'c Context<'r>, &str, &str) -> Result<std::string::String, Value<'r>> + Send;
+type ModuleLoader = dyn for<'c, 'r> Fn(&'c Context<'r>, &str) -> Result<Value<'r>, Value<'r>> + Send;
+type InterruptHandler = dyn FnMut() -> bool + Send;
+type ClassNameFn = unsafe fn(*const std::ffi::c_void) -> *const str;
+type PromiseRejectionTracker = dyn for<'c, 'r> Fn(&'c Context<'r>, &Value<'r>, &Value<'r>, bool) + Send;
+
+// deadline of an eval_global_with_timeout call, lives on its stack frame
+struct EvalDeadline {
+    at: Instant,
+    fired: Cell<bool>,
+    // deadline of the enclosing timed eval, still enforced while this one runs
+    outer: Option<NonNull<EvalDeadline>>,
+}
+
+#[allow(clippy::large_enum_variant)]
 enum RuntimeStore {
     Running {
         class_ids: RefCell<HashMap<TypeId, u32>>,
+        class_names: RefCell<HashMap<u32, ClassNameFn>>,
         global_contexts: RefCell<GlobalHolder<NonNull<rquickjs_sys::JSContext>>>,
         global_refs: RefCell<GlobalHolder<rquickjs_sys::JSValue>>,
         global_atoms: RefCell<GlobalHolder<rquickjs_sys::JSAtom>>,
+        module_resolver: RefCell<Option<Box<ModuleResolver>>>,
+        module_loader: RefCell<Option<Box<ModuleLoader>>>,
+        interrupt_handler: RefCell<Option<Box<InterruptHandler>>>,
+        eval_deadline: Cell<Option<NonNull<EvalDeadline>>>,
+        promise_rejection_tracker: RefCell<Option<Rc<PromiseRejectionTracker>>>,
+        debug_redaction: Cell<bool>,
+        debug_context: Cell<Option<NonNull<rquickjs_sys::JSContext>>>,
+        parked_contexts: RefCell<Vec<NonNull<rquickjs_sys::JSContext>>>,
+        job_depth: Cell<usize>,
+        user_data: RefCell<HashMap<TypeId, Box<dyn Any + Send>>>,
+        call_depth: Cell<usize>,
     },
     Destroying {
         class_ids: HashMap<TypeId, u32>,
@@ -141,6 +232,22 @@ impl Drop for Runtime {
         unsafe {
             let store_ptr = &mut *(JS_GetRuntimeOpaque(self.ptr.as_ptr()) as *mut RuntimeStore);
 
+            if let RuntimeStore::Running {
+                debug_context,
+                parked_contexts,
+                ..
+            } = store_ptr
+            {
+                if let Some(ctx) = debug_context.take() {
+                    JS_FreeContext(ctx.as_ptr());
+                }
+
+                // JS_FreeRuntime frees the jobs still referring to these without running them
+                for ctx in parked_contexts.take() {
+                    JS_FreeContext(ctx.as_ptr());
+                }
+            }
+
             *store_ptr = RuntimeStore::Destroying {
                 class_ids: match store_ptr {
                     RuntimeStore::Running { class_ids, .. } => class_ids.take(),
@@ -168,9 +275,21 @@ impl Runtime {
 
             let store = RuntimeStore::Running {
                 class_ids: RefCell::new(HashMap::new()),
+                class_names: RefCell::new(HashMap::new()),
                 global_contexts: RefCell::new(GlobalHolder::new(ptr, |_, ctx| JS_FreeContext(ctx.as_ptr()))),
                 global_refs: RefCell::new(GlobalHolder::new(ptr, |rt, value| JS_FreeValueRT(rt.as_ptr(), value))),
                 global_atoms: RefCell::new(GlobalHolder::new(ptr, |rt, value| JS_FreeAtomRT(rt.as_ptr(), value))),
+                module_resolver: RefCell::new(None),
+                module_loader: RefCell::new(None),
+                interrupt_handler: RefCell::new(None),
+                eval_deadline: Cell::new(None),
+                promise_rejection_tracker: RefCell::new(None),
+                debug_redaction: Cell::new(false),
+                debug_context: Cell::new(None),
+                parked_contexts: RefCell::new(Vec::new()),
+                job_depth: Cell::new(0),
+                user_data: RefCell::new(HashMap::new()),
+                call_depth: Cell::new(0),
             };
 
             JS_SetRuntimeOpaque(ptr.as_ptr(), Box::into_raw(Box::new(store)) as *mut std::ffi::c_void);
@@ -179,6 +298,38 @@ impl Runtime {
         }
     }
 
+    /// # Safety
+    ///
+    /// `value` must be a live value owned by this runtime, it is borrowed and the returned `Value` holds its own reference.
+    pub unsafe fn dup_raw(&self, value: rquickjs_sys::JSValue) -> Value<'_> {
+        unsafe { Value::from_raw(self, JS_DupValueRT(self.ptr.as_ptr(), value)).expect("dup exception value") }
+    }
+
+    pub fn try_shutdown(self) -> Result<(), OutstandingHandles> {
+        let outstanding = match self.store() {
+            RuntimeStore::Running {
+                global_contexts,
+                global_refs,
+                global_atoms,
+                ..
+            } => OutstandingHandles {
+                values: global_refs.borrow().live_count(),
+                contexts: global_contexts.borrow().live_count(),
+                atoms: global_atoms.borrow().live_count(),
+            },
+            RuntimeStore::Destroying { .. } => panic!("runtime destroying"),
+        };
+
+        // handles still alive are released anyway, they can no longer be resolved against this runtime
+        drop(self);
+
+        if outstanding.values == 0 && outstanding.contexts == 0 && outstanding.atoms == 0 {
+            Ok(())
+        } else {
+            Err(outstanding)
+        }
+    }
+
     pub fn as_raw(&self) -> NonNull<rquickjs_sys::JSRuntime> {
         self.ptr
     }
@@ -191,7 +342,28 @@ impl Runtime {
         }
     }
 
+    fn call_depth(&self) -> &Cell<usize> {
+        match self.store() {
+            RuntimeStore::Running { call_depth, .. } => call_depth,
+            RuntimeStore::Destroying { .. } => panic!("runtime destroying"),
+        }
+    }
+
+    fn job_depth(&self) -> &Cell<usize> {
+        match self.store() {
+            RuntimeStore::Running { job_depth, .. } => job_depth,
+            RuntimeStore::Destroying { .. } => panic!("runtime destroying"),
+        }
+    }
+
     pub fn run_gc(&self) {
+        self.cleanup_globals();
+
+        unsafe { JS_RunGC(self.ptr.as_ptr()) }
+    }
+
+    // releases the references of dropped global handles
+    fn cleanup_globals(&self) {
         match self.store() {
             RuntimeStore::Running {
                 global_contexts,
@@ -205,8 +377,102 @@ impl Runtime {
             }
             RuntimeStore::Destroying { .. } => {}
         }
+    }
 
-        unsafe { JS_RunGC(self.ptr.as_ptr()) }
+    // redacted `Debug` prints only the tag and pointer of reference values
+    pub fn set_debug_redaction(&self, redact: bool) {
+        match self.store() {
+            RuntimeStore::Running { debug_redaction, .. } => debug_redaction.set(redact),
+            RuntimeStore::Destroying { .. } => panic!("runtime destroying"),
+        }
+    }
+
+    pub(crate) fn is_debug_redacted(&self) -> bool {
+        match self.store() {
+            RuntimeStore::Running { debug_redaction, .. } => debug_redaction.get(),
+            RuntimeStore::Destroying { .. } => true,
+        }
+    }
+
+    pub fn compute_memory_usage(&self) -> MemoryUsage {
+        let usage = unsafe {
+            let mut usage = std::mem::zeroed::<rquickjs_sys::JSMemoryUsage>();
+            JS_ComputeMemoryUsage(self.ptr.as_ptr(), &mut usage);
+            usage
+        };
+
+        MemoryUsage {
+            malloc_size: usage.malloc_size,
+            malloc_limit: usage.malloc_limit,
+            memory_used_size: usage.memory_used_size,
+            malloc_count: usage.malloc_count,
+            memory_used_count: usage.memory_used_count,
+            atom_count: usage.atom_count,
+            atom_size: usage.atom_size,
+            str_count: usage.str_count,
+            str_size: usage.str_size,
+            obj_count: usage.obj_count,
+            obj_size: usage.obj_size,
+            prop_count: usage.prop_count,
+            prop_size: usage.prop_size,
+            shape_count: usage.shape_count,
+            shape_size: usage.shape_size,
+            js_func_count: usage.js_func_count,
+            js_func_size: usage.js_func_size,
+            js_func_code_size: usage.js_func_code_size,
+            js_func_pc2line_count: usage.js_func_pc2line_count,
+            js_func_pc2line_size: usage.js_func_pc2line_size,
+            c_func_count: usage.c_func_count,
+            array_count: usage.array_count,
+            fast_array_count: usage.fast_array_count,
+            fast_array_elements: usage.fast_array_elements,
+            binary_object_count: usage.binary_object_count,
+            binary_object_size: usage.binary_object_size,
+        }
+    }
+
+    // panics while a guard returned by `user_data` is alive
+    pub fn set_user_data<T: Send + 'static>(&self, data: T) -> Option<T> {
+        match self.store() {
+            RuntimeStore::Running { user_data, .. } => user_data
+                .borrow_mut()
+                .insert(TypeId::of::<T>(), Box::new(data))
+                .map(|old| *old.downcast::<T>().expect("user data keyed by another type")),
+            RuntimeStore::Destroying { .. } => panic!("runtime destroying"),
+        }
+    }
+
+    pub fn user_data<T: Send + 'static>(&self) -> Option<Ref<'_, T>> {
+        match self.store() {
+            RuntimeStore::Running { user_data, .. } => {
+                Ref::filter_map(user_data.borrow(), |data| data.get(&TypeId::of::<T>())?.downcast_ref::<T>()).ok()
+            }
+            RuntimeStore::Destroying { .. } => None,
+        }
+    }
+
+    // shared by Debug impls, a raw context is enough to stringify values and is created only once
+    pub(crate) fn debug_context(&self) -> Option<NonNull<rquickjs_sys::JSContext>> {
+        match self.store() {
+            RuntimeStore::Running { debug_context, .. } => {
+                if debug_context.get().is_none() {
+                    debug_context.set(NonNull::new(unsafe { JS_NewContextRaw(self.ptr.as_ptr()) }));
+                }
+
+                debug_context.get()
+            }
+            RuntimeStore::Destroying { .. } => None,
+        }
+    }
+
+    pub fn gc_threshold(&self) -> usize {
+        unsafe { JS_GetGCThreshold(self.ptr.as_ptr()) as _ }
+    }
+
+    pub fn set_gc_threshold(&self, bytes: usize) {
+        unsafe {
+            JS_SetGCThreshold(self.ptr.as_ptr(), bytes as _);
+        }
     }
 
     pub fn set_max_stack_size(&self, size: Option<NonZeroUsize>) {
@@ -215,6 +481,12 @@ impl Runtime {
         }
     }
 
+    pub fn set_memory_limit(&self, limit: Option<NonZeroUsize>) {
+        unsafe {
+            JS_SetMemoryLimit(self.ptr.as_ptr(), limit.map(|s| s.get() as _).unwrap_or(0));
+        }
+    }
+
     pub fn update_stack_top(&self) {
         unsafe {
             JS_UpdateStackTop(self.ptr.as_ptr());
@@ -226,15 +498,29 @@ impl Runtime {
 
         self.update_stack_top();
 
-        Context { rt: self, ptr: ctx_ptr }
+        Context {
+            rt: self,
+            ptr: ctx_ptr,
+            global: OnceCell::new(),
+        }
+    }
+
+    // same as new_context, which already adds every standard intrinsic
+    pub fn new_full_context(&self) -> Context<'_> {
+        self.new_context()
     }
 
+    // no intrinsics at all, not even Object, see Context::add_intrinsic
     pub fn new_plain_context(&self) -> Context<'_> {
         let ctx_ptr = unsafe { enforce_not_out_of_memory(JS_NewContextRaw(self.ptr.as_ptr())) };
 
         self.update_stack_top();
 
-        Context { rt: self, ptr: ctx_ptr }
+        Context {
+            rt: self,
+            ptr: ctx_ptr,
+            global: OnceCell::new(),
+        }
     }
 
     pub fn new_global_context(&self, ctx: &Context) -> Result<GlobalContext, InvalidRuntime> {
@@ -254,13 +540,88 @@ impl Runtime {
         }
     }
 
+    // exceptions of failing jobs are discarded, see execute_pending_job to observe them
     pub fn execute_pending_jobs(&self) {
+        self.drain_pending_jobs(usize::MAX);
+    }
+
+    // runs a single job, Ok(false) when the queue is empty
+    pub fn execute_pending_job(&self) -> Result<bool, (Context<'_>, Value<'_>)> {
+        let depth = self.job_depth();
+        depth.set(depth.get() + 1);
+
+        let ret = unsafe {
+            let mut ctx = std::ptr::null_mut();
+            match JS_ExecutePendingJob(self.ptr.as_ptr(), &mut ctx) {
+                0 => Ok(false),
+                ret if ret < 0 => {
+                    let ctx = Context {
+                        rt: self,
+                        ptr: enforce_not_out_of_memory(JS_DupContext(ctx)),
+                        global: OnceCell::new(),
+                    };
+                    let err = Value::from_raw(self, JS_GetException(ctx.ptr.as_ptr())).unwrap();
+
+                    Err((ctx, err))
+                }
+                _ => Ok(true),
+            }
+        };
+
+        depth.set(depth.get() - 1);
+        self.release_parked_contexts();
+
+        ret
+    }
+
+    // runs up to `limit` pending jobs, discarding the exceptions of failing ones, returns how many ran
+    fn drain_pending_jobs(&self, limit: usize) -> usize {
+        let depth = self.job_depth();
+        depth.set(depth.get() + 1);
+
+        let mut count = 0;
+
         unsafe {
             let mut ctx = std::ptr::null_mut();
-            while JS_ExecutePendingJob(self.ptr.as_ptr(), &mut ctx) != 0 {
-                let _ = ctx; // borrow only
+            while count < limit {
+                match JS_ExecutePendingJob(self.ptr.as_ptr(), &mut ctx) {
+                    0 => break,
+                    ret if ret < 0 => JS_FreeValueRT(self.ptr.as_ptr(), JS_GetException(ctx)),
+                    _ => {}
+                }
+
+                count += 1;
             }
         }
+
+        depth.set(depth.get() - 1);
+        self.release_parked_contexts();
+
+        count
+    }
+
+    // keeps a context alive until the job queue drains or the runtime is freed
+    fn park_context(&self, ctx: NonNull<rquickjs_sys::JSContext>) {
+        match self.store() {
+            RuntimeStore::Running { parked_contexts, .. } => parked_contexts.borrow_mut().push(ctx),
+            RuntimeStore::Destroying { .. } => panic!("runtime destroying"),
+        }
+    }
+
+    fn release_parked_contexts(&self) {
+        // a running job may still use its context, only the outermost drain releases them
+        if self.job_depth().get() != 0 || unsafe { JS_IsJobPending(self.ptr.as_ptr()) } {
+            return;
+        }
+
+        let parked = match self.store() {
+            RuntimeStore::Running { parked_contexts, .. } => parked_contexts.take(),
+            RuntimeStore::Destroying { .. } => panic!("runtime destroying"),
+        };
+
+        for ctx in parked {
+            unsafe { JS_FreeContext(ctx.as_ptr()) }
+        }
     }
 
     pub fn new_global_value(&self, value: &Value) -> Result<GlobalValue, InvalidRuntime> {
@@ -280,15 +641,257 @@ impl Runtime {
         }
     }
 
+    pub fn set_module_resolver<F>(&self, resolver: F)
+    where
+        F: for<'c, 'r> Fn(&'c Context<'r>, &str, &str) -> Result<std::string::String, Value<'r>> + Send + 'static,
+    {
+        match self.store() {
+            RuntimeStore::Running { module_resolver, .. } => {
+                *module_resolver.borrow_mut() = Some(Box::new(resolver));
+            }
+            RuntimeStore::Destroying { .. } => panic!("runtime destroying"),
+        }
+
+        self.install_module_loader_func();
+    }
+
+    pub fn set_module_loader<F>(&self, loader: F)
+    where
+        F: for<'c, 'r> Fn(&'c Context<'r>, &str) -> Result<Value<'r>, Value<'r>> + Send + 'static,
+    {
+        match self.store() {
+            RuntimeStore::Running { module_loader, .. } => {
+                *module_loader.borrow_mut() = Some(Box::new(loader));
+            }
+            RuntimeStore::Destroying { .. } => panic!("runtime destroying"),
+        }
+
+        self.install_module_loader_func();
+    }
+
+    // returning true aborts the running script with an uncatchable InternalError
+    pub fn set_interrupt_handler<F>(&self, handler: F)
+    where
+        F: FnMut() -> bool + Send + 'static,
+    {
+        match self.store() {
+            RuntimeStore::Running { interrupt_handler, .. } => {
+                *interrupt_handler.borrow_mut() = Some(Box::new(handler));
+            }
+            RuntimeStore::Destroying { .. } => panic!("runtime destroying"),
+        }
+
+        self.install_interrupt_handler_func();
+    }
+
+    pub fn clear_interrupt_handler(&self) {
+        match self.store() {
+            RuntimeStore::Running { interrupt_handler, .. } => {
+                interrupt_handler.borrow_mut().take();
+            }
+            RuntimeStore::Destroying { .. } => panic!("runtime destroying"),
+        }
+
+        self.install_interrupt_handler_func();
+    }
+
+    fn poll_interrupt_handler(&self) -> bool {
+        match self.store() {
+            // a handler evaluating code re-enters here, never interrupt from the nested call
+            RuntimeStore::Running { interrupt_handler, .. } => match interrupt_handler.try_borrow_mut() {
+                Ok(mut handler) => handler.as_mut().is_some_and(|handler| handler()),
+                Err(_) => false,
+            },
+            RuntimeStore::Destroying { .. } => false,
+        }
+    }
+
+    fn eval_deadline(&self) -> &Cell<Option<NonNull<EvalDeadline>>> {
+        match self.store() {
+            RuntimeStore::Running { eval_deadline, .. } => eval_deadline,
+            RuntimeStore::Destroying { .. } => panic!("runtime destroying"),
+        }
+    }
+
+    fn poll_eval_deadlines(&self) -> bool {
+        let mut deadline = match self.store() {
+            RuntimeStore::Running { eval_deadline, .. } => eval_deadline.get(),
+            RuntimeStore::Destroying { .. } => None,
+        };
+
+        let now = Instant::now();
+        while let Some(current) = deadline {
+            let current = unsafe { current.as_ref() };
+            if now >= current.at {
+                current.fired.set(true);
+
+                return true;
+            }
+
+            deadline = current.outer;
+        }
+
+        false
+    }
+
+    fn install_interrupt_handler_func(&self) {
+        unsafe extern "C" fn interrupt(rt: *mut rquickjs_sys::JSRuntime, _: *mut std::ffi::c_void) -> std::ffi::c_int {
+            let rt = ManuallyDrop::new(Runtime {
+                ptr: NonNull::new(rt).unwrap(),
+            });
+
+            (rt.poll_eval_deadlines() || rt.poll_interrupt_handler()) as _
+        }
+
+        let installed = match self.store() {
+            RuntimeStore::Running {
+                interrupt_handler,
+                eval_deadline,
+                ..
+            } => interrupt_handler.borrow().is_some() || eval_deadline.get().is_some(),
+            RuntimeStore::Destroying { .. } => false,
+        };
+
+        unsafe {
+            if installed {
+                JS_SetInterruptHandler(self.ptr.as_ptr(), Some(interrupt), std::ptr::null_mut());
+            } else {
+                JS_SetInterruptHandler(self.ptr.as_ptr(), None, std::ptr::null_mut());
+            }
+        }
+    }
+
+    // also called with is_handled = true when a handler is attached after the rejection
+    pub fn set_promise_rejection_tracker<F>(&self, tracker: F)
+    where
+        F: for<'c, 'r> Fn(&'c Context<'r>, &Value<'r>, &Value<'r>, bool) + Send + 'static,
+    {
+        unsafe extern "C" fn track(
+            ctx: *mut rquickjs_sys::JSContext,
+            promise: rquickjs_sys::JSValue,
+            reason: rquickjs_sys::JSValue,
+            is_handled: bool,
+            _: *mut std::ffi::c_void,
+        ) {
+            unsafe {
+                let rt = ManuallyDrop::new(Runtime {
+                    ptr: NonNull::new(JS_GetRuntime(ctx)).unwrap(),
+                });
+                let ctx = ManuallyDrop::new(Context {
+                    rt: &rt,
+                    ptr: NonNull::new(ctx).unwrap(),
+                    global: OnceCell::new(),
+                });
+
+                // released before the call, the tracker may replace itself
+                let tracker = match rt.store() {
+                    RuntimeStore::Running {
+                        promise_rejection_tracker,
+                        ..
+                    } => promise_rejection_tracker.borrow().clone(),
+                    RuntimeStore::Destroying { .. } => return,
+                };
+
+                if let Some(tracker) = tracker {
+                    tracker(&ctx, &rt.dup_raw(promise), &rt.dup_raw(reason), is_handled);
+                }
+            }
+        }
+
+        match self.store() {
+            RuntimeStore::Running {
+                promise_rejection_tracker,
+                ..
+            } => {
+                *promise_rejection_tracker.borrow_mut() = Some(Rc::new(tracker));
+            }
+            RuntimeStore::Destroying { .. } => panic!("runtime destroying"),
+        }
+
+        unsafe { JS_SetHostPromiseRejectionTracker(self.ptr.as_ptr(), Some(track), std::ptr::null_mut()) }
+    }
+
+    fn install_module_loader_func(&self) {
+        unsafe extern "C" fn normalize(
+            ctx: *mut rquickjs_sys::JSContext,
+            base: *const std::ffi::c_char,
+            name: *const std::ffi::c_char,
+            _: *mut std::ffi::c_void,
+        ) -> *mut std::ffi::c_char {
+            unsafe {
+                let rt = ManuallyDrop::new(Runtime {
+                    ptr: NonNull::new(JS_GetRuntime(ctx)).unwrap(),
+                });
+                let ctx = ManuallyDrop::new(Context {
+                    rt: &rt,
+                    ptr: NonNull::new(ctx).unwrap(),
+                    global: OnceCell::new(),
+                });
+
+                let base = CStr::from_ptr(base).to_string_lossy();
+                let name = CStr::from_ptr(name).to_string_lossy();
+
+                match ctx.resolve_module_name(&base, &name) {
+                    Ok(resolved) => js_strndup(ctx.ptr.as_ptr(), resolved.as_ptr() as _, resolved.len() as _),
+                    Err(err) => {
+                        JS_Throw(ctx.ptr.as_ptr(), err.into_raw());
+
+                        std::ptr::null_mut()
+                    }
+                }
+            }
+        }
+
+        unsafe extern "C" fn load(
+            ctx: *mut rquickjs_sys::JSContext,
+            name: *const std::ffi::c_char,
+            _: *mut std::ffi::c_void,
+        ) -> *mut rquickjs_sys::JSModuleDef {
+            unsafe {
+                let rt = ManuallyDrop::new(Runtime {
+                    ptr: NonNull::new(JS_GetRuntime(ctx)).unwrap(),
+                });
+                let ctx = ManuallyDrop::new(Context {
+                    rt: &rt,
+                    ptr: NonNull::new(ctx).unwrap(),
+                    global: OnceCell::new(),
+                });
+
+                let name = CStr::from_ptr(name).to_string_lossy();
+
+                match ctx.load_module(&name) {
+                    Ok(module) => module,
+                    Err(err) => {
+                        JS_Throw(ctx.ptr.as_ptr(), err.into_raw());
+
+                        std::ptr::null_mut()
+                    }
+                }
+            }
+        }
+
+        unsafe { JS_SetModuleLoaderFunc(self.ptr.as_ptr(), Some(normalize), Some(load), std::ptr::null_mut()) }
+    }
+
     fn get_or_alloc_class_id<C: Class>(&self) -> rquickjs_sys::JSClassID {
         let store = self.store();
 
         match store {
-            RuntimeStore::Running { class_ids, .. } => match class_ids.borrow_mut().entry(TypeId::of::<C>()) {
+            RuntimeStore::Running {
+                class_ids, class_names, ..
+            } => match class_ids.borrow_mut().entry(TypeId::of::<C>()) {
                 Entry::Occupied(o) => *o.get(),
                 Entry::Vacant(v) => {
                     let mut id = 0;
-                    unsafe { v.insert(JS_NewClassID(self.as_raw().as_ptr(), &mut id)).clone() }
+                    let id = unsafe { v.insert(JS_NewClassID(self.as_raw().as_ptr(), &mut id)).clone() };
+
+                    unsafe fn class_name<C: Class>(opaque: *const std::ffi::c_void) -> *const str {
+                        unsafe { (*(opaque as *const C)).class_name() }
+                    }
+
+                    class_names.borrow_mut().insert(id, class_name::<C>);
+
+                    id
                 }
             },
             RuntimeStore::Destroying { class_ids } => class_ids
@@ -297,11 +900,26 @@ impl Runtime {
                 .clone(),
         }
     }
+
+    fn get_class_id<C: Class>(&self) -> Option<rquickjs_sys::JSClassID> {
+        match self.store() {
+            RuntimeStore::Running { class_ids, .. } => class_ids.borrow().get(&TypeId::of::<C>()).copied(),
+            RuntimeStore::Destroying { class_ids } => class_ids.get(&TypeId::of::<C>()).copied(),
+        }
+    }
 }
 
 pub struct Context<'rt> {
     rt: &'rt Runtime,
     ptr: NonNull<rquickjs_sys::JSContext>,
+    // borrowed from the context itself, which keeps the global object alive, never freed by drop
+    global: OnceCell<Value<'rt>>,
+}
+
+impl<'rt> Debug for Context<'rt> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_fmt(format_args!("Context(ptr: {:?})", self.ptr))
+    }
 }
 
 impl<'rt> Clone for Context<'rt> {
@@ -309,18 +927,25 @@ impl<'rt> Clone for Context<'rt> {
         Self {
             rt: self.rt,
             ptr: unsafe { enforce_not_out_of_memory(JS_DupContext(self.ptr.as_ptr())) },
+            global: OnceCell::new(),
         }
     }
 }
 
 impl<'rt> Drop for Context<'rt> {
     fn drop(&mut self) {
-        // Execute all pending jobs to avoid dangling context pointers in jobs list
-        self.rt.execute_pending_jobs();
+        if let Some(global) = self.global.take() {
+            std::mem::forget(global);
+        }
 
-        unsafe { JS_FreeContext(self.ptr.as_ptr()) }
+        // queued jobs refer to their context without holding a reference, keep ours until the queue drains
+        if self.rt.job_depth().get() != 0 || unsafe { JS_IsJobPending(self.rt.ptr.as_ptr()) } {
+            self.rt.park_context(self.ptr);
+        } else {
+            unsafe { JS_FreeContext(self.ptr.as_ptr()) }
+        }
 
-        self.rt.run_gc();
+        self.rt.cleanup_globals();
     }
 }
 
@@ -358,6 +983,52 @@ pub struct OwnAtom<'rt> {
     pub is_enumerable: bool,
 }
 
+pub struct OwnAtomIter<'rt> {
+    rt: &'rt Runtime,
+    ptr: *mut rquickjs_sys::JSPropertyEnum,
+    length: u32,
+    index: u32,
+}
+
+impl<'rt> Iterator for OwnAtomIter<'rt> {
+    type Item = OwnAtom<'rt>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.index >= self.length {
+            return None;
+        }
+
+        // ownership of the atom moves to the item, drop only frees the remaining ones
+        let current = unsafe { &*self.ptr.offset(self.index as isize) };
+        self.index += 1;
+
+        Some(OwnAtom {
+            atom: unsafe { Atom::from_raw(self.rt, current.atom) },
+            is_enumerable: current.is_enumerable,
+        })
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let remaining = (self.length - self.index) as usize;
+        (remaining, Some(remaining))
+    }
+}
+
+impl<'rt> ExactSizeIterator for OwnAtomIter<'rt> {}
+
+impl<'rt> Drop for OwnAtomIter<'rt> {
+    fn drop(&mut self) {
+        unsafe {
+            for i in self.index..self.length {
+                JS_FreeAtomRT(self.rt.as_raw().as_ptr(), (*self.ptr.offset(i as isize)).atom);
+            }
+
+            // freed through the runtime, the iterator may outlive the context that listed the properties
+            js_free_rt(self.rt.as_raw().as_ptr(), self.ptr as _);
+        }
+    }
+}
+
 bitflags! {
     #[derive(Copy, Clone, Default)]
     pub struct GetOwnAtomFlags: u32 {
@@ -397,6 +1068,31 @@ pub struct PropertyDescriptor<'rt> {
     pub flags: PropertyDescriptorFlags,
 }
 
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SourceInfo {
+    pub filename: std::string::String,
+    pub line: u32,
+    pub column: u32,
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum JsKind {
+    Undefined,
+    Null,
+    Bool,
+    Number,
+    BigInt,
+    String,
+    Symbol,
+    Object,
+    Array,
+    TypedArray,
+    Function,
+    Module,
+    FunctionByteCode,
+    Internal,
+}
+
 #[derive(Copy, Clone, Debug, PartialEq, Eq)]
 pub enum PromiseState {
     Pending,
@@ -415,6 +1111,102 @@ impl Display for NotAPromise {
 
 impl std::error::Error for NotAPromise {}
 
+#[derive(Clone, Debug, PartialEq)]
+pub enum EvalError<'rt> {
+    Compile(Value<'rt>),
+    Runtime(Value<'rt>),
+}
+
+impl<'rt> EvalError<'rt> {
+    pub fn value(&self) -> &Value<'rt> {
+        match self {
+            EvalError::Compile(v) | EvalError::Runtime(v) => v,
+        }
+    }
+
+    pub fn into_value(self) -> Value<'rt> {
+        match self {
+            EvalError::Compile(v) | EvalError::Runtime(v) => v,
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum EvalTimeout<'rt> {
+    TimedOut,
+    Exception(Value<'rt>),
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum BufferAllocError<'rt> {
+    OutOfMemory(Value<'rt>),
+    Exception(Value<'rt>),
+}
+
+impl<'rt> From<BufferAllocError<'rt>> for Value<'rt> {
+    fn from(err: BufferAllocError<'rt>) -> Self {
+        match err {
+            BufferAllocError::OutOfMemory(v) | BufferAllocError::Exception(v) => v,
+        }
+    }
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct AnalysisReport {
+    // the compiler stops at the first violation
+    pub violation: Option<AnalysisViolation>,
+}
+
+impl AnalysisReport {
+    pub fn is_clean(&self) -> bool {
+        self.violation.is_none()
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct AnalysisViolation {
+    pub kind: std::string::String,
+    pub message: std::string::String,
+    pub stack: Option<std::string::String>,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct ThrownValue<'rt>(pub Value<'rt>);
+
+impl<'rt> ThrownValue<'rt> {
+    pub fn value(&self) -> &Value<'rt> {
+        &self.0
+    }
+
+    pub fn into_value(self) -> Value<'rt> {
+        self.0
+    }
+
+    pub fn is_error(&self, ctx: &Context<'rt>) -> bool {
+        ctx.is_error(&self.0)
+    }
+
+    pub fn message(&self, ctx: &Context<'rt>) -> Option<std::string::String> {
+        ctx.exception_message(&self.0)
+    }
+
+    pub fn stack(&self, ctx: &Context<'rt>) -> Option<std::string::String> {
+        ctx.exception_stack(&self.0)
+    }
+}
+
+impl<'rt> From<Value<'rt>> for ThrownValue<'rt> {
+    fn from(value: Value<'rt>) -> Self {
+        Self(value)
+    }
+}
+
+impl<'rt> From<ThrownValue<'rt>> for Value<'rt> {
+    fn from(value: ThrownValue<'rt>) -> Self {
+        value.0
+    }
+}
+
 #[derive(Copy, Clone, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
 pub struct TypedArrayType(rquickjs_sys::JSTypedArrayEnum);
 
@@ -428,8 +1220,23 @@ impl TypedArrayType {
     pub const UINT32: TypedArrayType = TypedArrayType(rquickjs_sys::JSTypedArrayEnum_JS_TYPED_ARRAY_UINT32);
     pub const BIG_INT64: TypedArrayType = TypedArrayType(rquickjs_sys::JSTypedArrayEnum_JS_TYPED_ARRAY_BIG_INT64);
     pub const BIG_UINT64: TypedArrayType = TypedArrayType(rquickjs_sys::JSTypedArrayEnum_JS_TYPED_ARRAY_BIG_UINT64);
+    pub const FLOAT16: TypedArrayType = TypedArrayType(rquickjs_sys::JSTypedArrayEnum_JS_TYPED_ARRAY_FLOAT16);
     pub const FLOAT32: TypedArrayType = TypedArrayType(rquickjs_sys::JSTypedArrayEnum_JS_TYPED_ARRAY_FLOAT32);
     pub const FLOAT64: TypedArrayType = TypedArrayType(rquickjs_sys::JSTypedArrayEnum_JS_TYPED_ARRAY_FLOAT64);
+
+    pub fn bytes_per_element(&self) -> usize {
+        match *self {
+            Self::UINT8C | Self::INT8 | Self::UINT8 => 1,
+            Self::INT16 | Self::UINT16 | Self::FLOAT16 => 2,
+            Self::INT32 | Self::UINT32 | Self::FLOAT32 => 4,
+            Self::BIG_INT64 | Self::BIG_UINT64 | Self::FLOAT64 => 8,
+            _ => panic!("unknown typed array type {}", self.0),
+        }
+    }
+
+    pub fn is_bigint(&self) -> bool {
+        matches!(*self, Self::BIG_INT64 | Self::BIG_UINT64)
+    }
 }
 
 bitflags! {
@@ -454,6 +1261,10 @@ impl<'rt> Context<'rt> {
         self.rt
     }
 
+    pub fn call_depth(&self) -> usize {
+        self.rt.call_depth().get()
+    }
+
     pub fn as_raw(&self) -> NonNull<rquickjs_sys::JSContext> {
         self.ptr
     }
@@ -494,6 +1305,10 @@ impl<'rt> Context<'rt> {
         }
     }
 
+    pub fn clear_exception(&self) {
+        let _ = self.catch();
+    }
+
     #[inline]
     fn try_catch<R>(&self, f: impl FnOnce() -> Result<R, Exception>) -> Result<R, Value<'rt>> {
         match f() {
@@ -546,13 +1361,241 @@ impl<'rt> Context<'rt> {
         self.eval(this, code, filename, flags.bits() | rquickjs_sys::JS_EVAL_TYPE_GLOBAL)
     }
 
-    pub fn eval_module(
-        &self,
-        code: impl AsRef<str>,
-        filename: impl AsRef<str>,
-        flags: EvalFlags,
-    ) -> Result<Value<'rt>, Value<'rt>> {
-        self.eval(None, code, filename, flags.bits() | rquickjs_sys::JS_EVAL_TYPE_MODULE)
+    pub fn eval_checked(
+        &self,
+        code: impl AsRef<str>,
+        filename: impl AsRef<str>,
+        flags: EvalFlags,
+    ) -> Result<Value<'rt>, EvalError<'rt>> {
+        let func = self
+            .eval_global(None, code, filename, flags | EvalFlags::COMPILE_ONLY)
+            .map_err(EvalError::Compile)?;
+
+        if flags.contains(EvalFlags::COMPILE_ONLY) {
+            return Ok(func);
+        }
+
+        self.eval_function(func).map_err(EvalError::Runtime)
+    }
+
+    // eval_function consumes the bytecode, clone it to run it repeatedly
+    pub fn compile(&self, code: impl AsRef<str>, filename: impl AsRef<str>, flags: EvalFlags) -> Result<Value<'rt>, Value<'rt>> {
+        self.eval_global(None, code, filename, flags | EvalFlags::COMPILE_ONLY)
+    }
+
+    pub fn compile_module(
+        &self,
+        code: impl AsRef<str>,
+        filename: impl AsRef<str>,
+        flags: EvalFlags,
+    ) -> Result<Value<'rt>, Value<'rt>> {
+        self.eval_module(code, filename, flags | EvalFlags::COMPILE_ONLY)
+    }
+
+    // violations that only surface at run time, like assignments to undeclared globals, are not reported
+    pub fn analyze(&self, code: impl AsRef<str>, filename: impl AsRef<str>) -> Result<AnalysisReport, Value<'rt>> {
+        let err = match self.compile(code, filename, EvalFlags::STRICT) {
+            Ok(_) => return Ok(AnalysisReport::default()),
+            Err(err) if self.is_error(&err) && !self.is_uncatchable_error(&err) => err,
+            Err(err) => return Err(err),
+        };
+
+        let kind = self.get_property_str(&err, "name")?;
+        let violation = AnalysisViolation {
+            kind: self.get_string_lossy(&kind)?,
+            message: self.exception_message(&err).unwrap_or_default(),
+            stack: self.exception_stack(&err),
+        };
+
+        Ok(AnalysisReport {
+            violation: Some(violation),
+        })
+    }
+
+    pub fn eval_global_with_timeout(
+        &self,
+        code: impl AsRef<str>,
+        filename: impl AsRef<str>,
+        flags: EvalFlags,
+        timeout: Duration,
+    ) -> Result<Value<'rt>, EvalTimeout<'rt>> {
+        let eval_deadline = self.rt.eval_deadline();
+
+        // nested timed evals stack up, every enclosing deadline keeps running
+        let deadline = EvalDeadline {
+            at: Instant::now() + timeout,
+            fired: Cell::new(false),
+            outer: eval_deadline.get(),
+        };
+
+        eval_deadline.set(Some(NonNull::from(&deadline)));
+        self.rt.install_interrupt_handler_func();
+
+        let ret = self.eval_global(None, code, filename, flags);
+
+        // deadline lives on this stack frame, never leave it installed
+        eval_deadline.set(deadline.outer);
+        self.rt.install_interrupt_handler_func();
+
+        ret.map_err(|err| {
+            if deadline.fired.get() {
+                EvalTimeout::TimedOut
+            } else {
+                EvalTimeout::Exception(err)
+            }
+        })
+    }
+
+    pub fn eval_module(
+        &self,
+        code: impl AsRef<str>,
+        filename: impl AsRef<str>,
+        flags: EvalFlags,
+    ) -> Result<Value<'rt>, Value<'rt>> {
+        let module = self.eval(
+            None,
+            code,
+            filename,
+            flags.bits() | rquickjs_sys::JS_EVAL_TYPE_MODULE | rquickjs_sys::JS_EVAL_FLAG_COMPILE_ONLY,
+        )?;
+
+        self.init_import_meta(&module)?;
+
+        if flags.contains(EvalFlags::COMPILE_ONLY) {
+            Ok(module)
+        } else {
+            self.eval_function(module)
+        }
+    }
+
+    pub fn eval_repl(&self, code: &str) -> Result<Value<'rt>, Value<'rt>> {
+        // quickjs-ng detects any code parsable as module as module, so prefer script when it compiles
+        match self.eval_global(None, code, "<repl>", EvalFlags::COMPILE_ONLY) {
+            Ok(script) => {
+                let ret = self.eval_function(script)?;
+
+                self.rt.drain_pending_jobs(usize::MAX);
+
+                return Ok(ret);
+            }
+            Err(err) if !detect_module(code) => return Err(err),
+            Err(_) => {}
+        }
+
+        let module = self.eval_module(code, "<repl>", EvalFlags::COMPILE_ONLY)?;
+        let Value::Module(m) = &module else {
+            unreachable!("compiled module is not a module")
+        };
+
+        // module definitions are owned by the context, so the pointer outlives the evaluation
+        let m = unsafe { m.as_raw().u.ptr as *mut rquickjs_sys::JSModuleDef };
+
+        let promise = self.eval_function(module)?;
+
+        self.rt.drain_pending_jobs(usize::MAX);
+
+        if let Ok(PromiseState::Rejected) = self.get_promise_state(&promise) {
+            return Err(self.get_promise_result(&promise));
+        }
+
+        self.try_catch(|| unsafe { Value::from_raw(self.rt, JS_GetModuleNamespace(self.ptr.as_ptr(), m)) })
+    }
+
+    // later entries overwrite earlier exports of the same name
+    pub fn merge_namespaces(&self, target: &Value, modules: &[Value]) -> Result<(), Value<'rt>> {
+        for module in modules {
+            let namespace = match module {
+                Value::Module(m) => {
+                    let m = unsafe { m.as_raw().u.ptr as *mut rquickjs_sys::JSModuleDef };
+
+                    self.try_catch(|| unsafe { Value::from_raw(self.rt, JS_GetModuleNamespace(self.ptr.as_ptr(), m)) })?
+                }
+                _ => unsafe { self.rt.dup_raw(module.as_raw()) },
+            };
+
+            for export in self.get_own_property_atoms(&namespace, GetOwnAtomFlags::STRING_MASK | GetOwnAtomFlags::ENUM_ONLY)? {
+                let value = self.get_property(&namespace, &export.atom)?;
+
+                self.set_property(target, &export.atom, value)?;
+            }
+        }
+
+        Ok(())
+    }
+
+    fn resolve_module_name(&self, base: &str, name: &str) -> Result<std::string::String, Value<'rt>> {
+        let resolver = match self.rt.store() {
+            RuntimeStore::Running { module_resolver, .. } => module_resolver.borrow(),
+            RuntimeStore::Destroying { .. } => panic!("runtime destroying"),
+        };
+
+        match &*resolver {
+            Some(resolver) => resolver(self, base, name),
+            None => Ok(normalize_module_name(base, name)),
+        }
+    }
+
+    fn load_module(&self, name: &str) -> Result<*mut rquickjs_sys::JSModuleDef, Value<'rt>> {
+        let loader = match self.rt.store() {
+            RuntimeStore::Running { module_loader, .. } => module_loader.borrow(),
+            RuntimeStore::Destroying { .. } => panic!("runtime destroying"),
+        };
+
+        let Some(loader) = &*loader else {
+            return self.try_catch(|| unsafe {
+                let name = self.new_c_string::<64>(name)?;
+
+                JS_ThrowReferenceError(self.ptr.as_ptr(), c"could not load module '%s'".as_ptr(), name.as_ptr());
+
+                Err(Exception)
+            });
+        };
+
+        match loader(self, name)? {
+            // runtime keeps its own reference to compiled modules, dropping ours is fine
+            Value::Module(m) => Ok(unsafe { m.as_raw().u.ptr } as *mut rquickjs_sys::JSModuleDef),
+            _ => self.try_catch(|| unsafe {
+                JS_ThrowTypeError(self.ptr.as_ptr(), c"module loader must return a compiled module".as_ptr());
+
+                Err(Exception)
+            }),
+        }
+    }
+
+    fn init_import_meta(&self, module: &Value) -> Result<(), Value<'rt>> {
+        let Value::Module(m) = module else {
+            return Ok(());
+        };
+
+        let (base, meta) = unsafe {
+            let m = m.as_raw().u.ptr as *mut rquickjs_sys::JSModuleDef;
+
+            let name = Atom::from_raw(self.rt, JS_GetModuleName(self.ptr.as_ptr(), m));
+            let name = self.atom_to_string(&name)?;
+            let base = self.get_string(&name)?.to_string_lossy().into_owned();
+
+            let meta = self.try_catch(|| Value::from_raw(self.rt, JS_GetImportMeta(self.ptr.as_ptr(), m)))?;
+
+            (base, meta)
+        };
+
+        let resolve = NativeFunction::new(move |ctx, _, _, args, _| {
+            let specifier = ctx.to_string(args.first().unwrap_or(&Value::Undefined))?;
+            let specifier = ctx.get_string_lossy(&specifier)?;
+
+            let resolved = ctx.resolve_module_name(&base, &specifier)?;
+
+            ctx.new_string(resolved)
+        });
+
+        self.define_property_value_str(
+            &meta,
+            "resolve",
+            self.new_object_class(resolve, None)?,
+            PropertyDescriptorFlags::CONFIGURABLE | PropertyDescriptorFlags::WRITABLE | PropertyDescriptorFlags::ENUMERABLE,
+        )?;
+
+        Ok(())
     }
 
     pub fn add_intrinsic(&self, intrinsics: Intrinsics) {
@@ -626,6 +1669,11 @@ impl<'rt> Context<'rt> {
         })
     }
 
+    pub fn to_uint32(&self, v: &Value) -> Result<u32, Value<'rt>> {
+        // ToUint32 and ToInt32 share the same modulo 2^32 conversion, same as JS_ToUint32 in quickjs.h
+        self.to_int32(v).map(|v| v as u32)
+    }
+
     pub fn to_int64(&self, v: &Value) -> Result<i64, Value<'rt>> {
         self.enforce_value_in_same_runtime(v);
 
@@ -654,6 +1702,17 @@ impl<'rt> Context<'rt> {
         })
     }
 
+    pub fn to_length(&self, v: &Value) -> Result<u64, Value<'rt>> {
+        const MAX_SAFE_INTEGER: f64 = ((1u64 << 53) - 1) as f64;
+
+        let v = self.to_float64(v)?;
+        if v.is_nan() || v <= 0.0 {
+            Ok(0)
+        } else {
+            Ok(v.trunc().min(MAX_SAFE_INTEGER) as u64)
+        }
+    }
+
     pub fn to_float64(&self, v: &Value) -> Result<f64, Value<'rt>> {
         self.enforce_value_in_same_runtime(v);
 
@@ -682,6 +1741,31 @@ impl<'rt> Context<'rt> {
         })
     }
 
+    pub fn number_to_string_radix(&self, value: &Value, radix: u32) -> Result<std::string::String, Value<'rt>> {
+        let number = self.get_property_str(&self.get_global_object(), "Number")?;
+        let proto = self.get_property_str(&number, "prototype")?;
+        let to_string = self.get_property_str(&proto, "toString")?;
+
+        let ret = self.call(&to_string, value, &[Value::Int32(radix as i32)])?;
+
+        self.get_string_lossy(&ret)
+    }
+
+    pub fn describe(&self, value: &Value) -> std::string::String {
+        if let Value::Symbol(_) = value {
+            return match self.symbol_description(value) {
+                Ok(Some(desc)) => format!("Symbol({})", desc),
+                _ => "Symbol()".to_string(),
+            };
+        }
+
+        // objects with a throwing or missing toString fall back to the [object Tag] form
+        self.to_string(value)
+            .or_else(|_| self.to_object_string(value))
+            .and_then(|s| self.get_string_lossy(&s))
+            .unwrap_or_else(|_| "<unprintable>".to_string())
+    }
+
     pub fn to_object(&self, value: &Value) -> Result<Value<'rt>, Value<'rt>> {
         self.enforce_value_in_same_runtime(value);
 
@@ -700,6 +1784,25 @@ impl<'rt> Context<'rt> {
         unsafe { JS_IsError(value.as_raw()) }
     }
 
+    pub fn exception_message(&self, err: &Value) -> Option<std::string::String> {
+        self.error_property(err, "message")
+    }
+
+    pub fn exception_stack(&self, err: &Value) -> Option<std::string::String> {
+        self.error_property(err, "stack")
+    }
+
+    fn error_property(&self, err: &Value, name: &str) -> Option<std::string::String> {
+        if !self.is_error(err) {
+            return None;
+        }
+
+        match self.get_property_str(err, name).ok()? {
+            value @ Value::String(_) => Some(self.get_string_lossy(&value).ok()?),
+            _ => None,
+        }
+    }
+
     pub fn is_uncatchable_error(&self, value: &Value) -> bool {
         self.enforce_value_in_same_runtime(value);
 
@@ -722,6 +1825,77 @@ impl<'rt> Context<'rt> {
         unsafe { self.try_catch(|| Value::from_raw(self.rt, JS_NewError(self.ptr.as_ptr()))) }
     }
 
+    pub fn new_error_with(&self, message: impl AsRef<str>) -> Result<Value<'rt>, Value<'rt>> {
+        let error = self.new_error()?;
+
+        self.define_property_value_str(
+            &error,
+            "message",
+            self.new_string(message)?,
+            PropertyDescriptorFlags::CONFIGURABLE | PropertyDescriptorFlags::WRITABLE,
+        )?;
+
+        #[cfg(all(feature = "backtrace", debug_assertions))]
+        self.attach_rust_backtrace(&error)?;
+
+        Ok(error)
+    }
+
+    #[cfg(all(feature = "backtrace", debug_assertions))]
+    fn attach_rust_backtrace(&self, error: &Value) -> Result<(), Value<'rt>> {
+        self.define_property_value_str(
+            error,
+            "rustBacktrace",
+            self.new_string(std::backtrace::Backtrace::force_capture().to_string())?,
+            PropertyDescriptorFlags::CONFIGURABLE | PropertyDescriptorFlags::WRITABLE,
+        )?;
+
+        Ok(())
+    }
+
+    pub fn throw_error(&self, message: impl AsRef<str>) -> Value<'rt> {
+        self.throw_native_error(JS_ThrowPlainError, message)
+    }
+
+    pub fn throw_type_error(&self, message: impl AsRef<str>) -> Value<'rt> {
+        self.throw_native_error(JS_ThrowTypeError, message)
+    }
+
+    pub fn throw_range_error(&self, message: impl AsRef<str>) -> Value<'rt> {
+        self.throw_native_error(JS_ThrowRangeError, message)
+    }
+
+    pub fn throw_syntax_error(&self, message: impl AsRef<str>) -> Value<'rt> {
+        self.throw_native_error(JS_ThrowSyntaxError, message)
+    }
+
+    pub fn throw_reference_error(&self, message: impl AsRef<str>) -> Value<'rt> {
+        self.throw_native_error(JS_ThrowReferenceError, message)
+    }
+
+    fn throw_native_error(
+        &self,
+        throw: unsafe extern "C" fn(*mut rquickjs_sys::JSContext, *const std::ffi::c_char, ...) -> rquickjs_sys::JSValue,
+        message: impl AsRef<str>,
+    ) -> Value<'rt> {
+        let ret: Result<(), Value<'rt>> = self.try_catch(|| unsafe {
+            let message = self.new_c_string::<64>(message)?;
+
+            // pass the message as an argument so '%' in it is not read as a format directive
+            throw(self.ptr.as_ptr(), c"%s".as_ptr(), message.as_ptr());
+
+            Err(Exception)
+        });
+        let error = ret.unwrap_err();
+
+        #[cfg(all(feature = "backtrace", debug_assertions))]
+        if let Err(err) = self.attach_rust_backtrace(&error) {
+            return err;
+        }
+
+        error
+    }
+
     pub fn is_function(&self, value: &Value) -> bool {
         self.enforce_value_in_same_runtime(value);
 
@@ -775,6 +1949,26 @@ impl<'rt> Context<'rt> {
         })
     }
 
+    pub fn new_string_utf16(&self, units: &[u16]) -> Result<Value<'rt>, Value<'rt>> {
+        // encode each code unit on its own, quickjs decodes lone surrogates back to the same unit
+        let mut buf = Vec::with_capacity(units.len() * 3);
+        for &unit in units {
+            match unit {
+                0..0x80 => buf.push(unit as u8),
+                0x80..0x800 => buf.extend_from_slice(&[0xC0 | (unit >> 6) as u8, 0x80 | (unit & 0x3F) as u8]),
+                _ => buf.extend_from_slice(&[
+                    0xE0 | (unit >> 12) as u8,
+                    0x80 | ((unit >> 6) & 0x3F) as u8,
+                    0x80 | (unit & 0x3F) as u8,
+                ]),
+            }
+        }
+
+        self.try_catch(|| unsafe {
+            Value::from_raw(self.rt, JS_NewStringLen(self.ptr.as_ptr(), buf.as_ptr() as _, buf.len() as _))
+        })
+    }
+
     pub fn get_string<'v>(&'v self, v: &'v Value) -> Result<JSStr<'v>, Value<'rt>> {
         self.enforce_value_in_same_runtime(v);
 
@@ -786,14 +1980,24 @@ impl<'rt> Context<'rt> {
                 return Err(self.catch().unwrap());
             }
 
+            let bytes = std::slice::from_raw_parts(ptr as *const u8, length as _);
+            let lossy = std::str::from_utf8(bytes)
+                .is_err()
+                .then(|| std::string::String::from_utf8_lossy(bytes).into());
+
             Ok(JSStr {
-                ctx: self,
+                ctx: &self.ptr,
                 ptr,
                 len: length as _,
+                lossy,
             })
         }
     }
 
+    pub fn get_string_lossy(&self, v: &Value) -> Result<std::string::String, Value<'rt>> {
+        Ok(self.get_string(v)?.to_string_lossy().into_owned())
+    }
+
     pub fn to_string(&self, value: &Value) -> Result<Value<'rt>, Value<'rt>> {
         self.enforce_value_in_same_runtime(value);
 
@@ -950,6 +2154,7 @@ impl<'rt> Context<'rt> {
                                 let ctx = ManuallyDrop::new(Context {
                                     rt: &rt,
                                     ptr: NonNull::new(ctx).unwrap(),
+                                    global: OnceCell::new(),
                                 });
 
                                 let data = JS_GetOpaque(func_obj, JS_GetClassID(func_obj)) as *mut C;
@@ -963,10 +2168,16 @@ impl<'rt> Context<'rt> {
                                     .into_iter()
                                     .map(|v| ManuallyDrop::new(Value::from_raw(&rt, argv.offset(v as _).read()).unwrap()))
                                     .collect::<MaybeTinyVec<_, 16>>();
+                                let constructor = (flags as u32) & rquickjs_sys::JS_CALL_FLAG_CONSTRUCTOR > 0;
                                 let options = CallOptions {
-                                    constructor: (flags as u32) & rquickjs_sys::JS_CALL_FLAG_CONSTRUCTOR > 0,
+                                    constructor,
+                                    // quickjs passes new.target as this_val when called as constructor
+                                    new_target: if constructor { Some(&this) } else { None },
                                 };
 
+                                let depth = rt.call_depth();
+                                depth.set(depth.get() + 1);
+
                                 let ret = match (*data).call(
                                     &ctx,
                                     &func,
@@ -978,6 +2189,8 @@ impl<'rt> Context<'rt> {
                                     Err(err) => JS_Throw(ctx.ptr.as_ptr(), err.into_raw()),
                                 };
 
+                                depth.set(depth.get() - 1);
+
                                 ret
                             }
                         }
@@ -1011,11 +2224,60 @@ impl<'rt> Context<'rt> {
         })
     }
 
+    pub fn new_null_proto_object(&self) -> Result<Value<'rt>, Value<'rt>> {
+        self.new_object(Some(&Value::Null))
+    }
+
+    pub fn new_frozen_object(&self, entries: &[(&str, Value)]) -> Result<Value<'rt>, Value<'rt>> {
+        let obj = self.new_object(None)?;
+
+        // non-writable and non-configurable from the start, only extensibility is left to seal
+        for (name, value) in entries {
+            self.define_property_value_str(
+                &obj,
+                name,
+                value.clone(),
+                PropertyDescriptorFlags::ENUMERABLE | PropertyDescriptorFlags::THROW,
+            )?;
+        }
+
+        self.prevent_extensions(&obj)?;
+
+        Ok(obj)
+    }
+
     pub fn new_object_class<C: Class>(&self, class: C, proto: Option<&Value>) -> Result<Value<'rt>, Value<'rt>> {
         if let Some(obj) = proto {
             self.enforce_value_in_same_runtime(obj);
         }
 
+        let name = match class.class_name() {
+            name if name == C::NAME => None,
+            name => Some(name.to_owned()),
+        };
+
+        // instances on the class prototype read their name through its tag getter
+        if name.is_some() && proto.is_none() {
+            self.install_class_name_tag::<C>()?;
+        }
+
+        let obj = self.alloc_object_class(class, proto)?;
+
+        if let Some(name) = name
+            && proto.is_some()
+        {
+            self.define_property_value(
+                &obj,
+                &self.to_string_tag_atom(),
+                self.new_string(name)?,
+                PropertyDescriptorFlags::CONFIGURABLE,
+            )?;
+        }
+
+        Ok(obj)
+    }
+
+    fn alloc_object_class<C: Class>(&self, class: C, proto: Option<&Value>) -> Result<Value<'rt>, Value<'rt>> {
         self.try_catch(|| unsafe {
             let class_id = self.get_or_register_class::<C>();
 
@@ -1030,6 +2292,82 @@ impl<'rt> Context<'rt> {
         })
     }
 
+    fn to_string_tag_atom(&self) -> Atom<'rt> {
+        unsafe {
+            Atom::from_raw(
+                self.rt,
+                JS_DupAtom(self.ptr.as_ptr(), rquickjs_sys::JS_ATOM_Symbol_toStringTag as _),
+            )
+        }
+    }
+
+    fn install_class_name_tag<C: Class>(&self) -> Result<(), Value<'rt>> {
+        let tag = self.to_string_tag_atom();
+
+        let proto = match self.get_class_proto::<C>() {
+            proto @ Value::Object(_) => {
+                let installed =
+                    unsafe { JS_GetOwnProperty(self.ptr.as_ptr(), std::ptr::null_mut(), proto.as_raw(), tag.as_raw()) };
+                if installed < 0 {
+                    return Err(self.catch().expect("failed to get error in get own property"));
+                }
+                if installed != 0 {
+                    return Ok(());
+                }
+
+                proto
+            }
+            _ => {
+                let proto = self.new_null_proto_object()?;
+                self.set_class_proto::<C>(proto.clone());
+                proto
+            }
+        };
+
+        let getter = NativeFunction::new(|ctx, _, this, _, _| match ctx.get_class_opaque::<C>(this) {
+            Some(class) if class.class_name() != C::NAME => ctx.new_string(class.class_name()),
+            _ => Ok(Value::Undefined),
+        });
+
+        self.define_property_getset(
+            &proto,
+            &tag,
+            self.alloc_object_class(getter, None)?,
+            Value::Undefined,
+            PropertyDescriptorFlags::CONFIGURABLE,
+        )?;
+
+        Ok(())
+    }
+
+    pub fn new_instance<C: Class>(&self, class: C) -> Result<(Value<'rt>, &C), Value<'rt>> {
+        let obj = self.new_object_class(class, None)?;
+        let opaque = self
+            .get_class_opaque::<C>(&obj)
+            .expect("opaque missing on new class instance");
+
+        Ok((obj, opaque))
+    }
+
+    pub fn new_instance_of<C: Class>(&self, ctor: &Value, class: C) -> Result<Value<'rt>, Value<'rt>> {
+        self.enforce_value_in_same_runtime(ctor);
+
+        // like OrdinaryCreateFromConstructor, a non-object prototype falls back to the class prototype
+        match self.get_property_str(ctor, "prototype")? {
+            proto @ Value::Object(_) => self.new_object_class(class, Some(&proto)),
+            _ => self.new_object_class(class, None),
+        }
+    }
+
+    pub fn construct_instance<C: Class>(&self, ctor: &Value, args: &[Value]) -> Result<(Value<'rt>, &C), Value<'rt>> {
+        let obj = self.call_constructor(ctor, None, args)?;
+
+        match self.get_class_opaque::<C>(&obj) {
+            Some(opaque) => Ok((obj, opaque)),
+            None => Err(self.throw_type_error(format!("constructor did not return a {} instance", C::NAME))),
+        }
+    }
+
     pub fn get_class_opaque<C: Class>(&self, value: &Value) -> Option<&C> {
         self.enforce_value_in_same_runtime(value);
 
@@ -1040,6 +2378,34 @@ impl<'rt> Context<'rt> {
         }
     }
 
+    pub fn is_instance_of_class<C: Class>(&self, value: &Value) -> bool {
+        self.enforce_value_in_same_runtime(value);
+
+        // a class that was never registered has no instances, and asking must not register it
+        match self.rt.get_class_id::<C>() {
+            Some(class_id) => unsafe { JS_GetClassID(value.as_raw()) == class_id },
+            None => false,
+        }
+    }
+
+    pub fn class_name_of<'v>(&self, value: &'v Value) -> Option<&'v str> {
+        self.enforce_value_in_same_runtime(value);
+
+        let class_names = match self.rt.store() {
+            RuntimeStore::Running { class_names, .. } => class_names,
+            RuntimeStore::Destroying { .. } => panic!("runtime destroying"),
+        };
+
+        unsafe {
+            let class_id = JS_GetClassID(value.as_raw());
+            let class_name = *class_names.borrow().get(&class_id)?;
+
+            // the opaque lives as long as the object, which `value` keeps alive
+            let opaque = JS_GetOpaque(value.as_raw(), class_id);
+            if opaque.is_null() { None } else { Some(&*class_name(opaque)) }
+        }
+    }
+
     pub fn set_constructor_bit(&self, value: &Value, is_constructor: bool) -> bool {
         self.enforce_value_in_same_runtime(value);
 
@@ -1072,6 +2438,39 @@ impl<'rt> Context<'rt> {
         }
     }
 
+    pub fn function_source_info(&self, func: &Value) -> Option<SourceInfo> {
+        if !self.is_function(func) {
+            return None;
+        }
+
+        // quickjs-ng exposes bytecode debug info through getters on Function.prototype,
+        // call them directly so own properties of func can not shadow them
+        let proto = self.get_function_proto();
+        let debug_info = |name: &str| {
+            let atom = self.new_atom(name).ok()?;
+            let desc = self.get_own_property(&proto, &atom).ok()?;
+
+            self.call(&desc.getter, func, &[]).ok()
+        };
+
+        let filename = match debug_info("fileName")? {
+            filename @ Value::String(_) => self.get_string_lossy(&filename).ok()?,
+            _ => return None,
+        };
+        let Value::Int32(line) = debug_info("lineNumber")? else {
+            return None;
+        };
+        let Value::Int32(column) = debug_info("columnNumber")? else {
+            return None;
+        };
+
+        Some(SourceInfo {
+            filename,
+            line: line as u32,
+            column: column as u32,
+        })
+    }
+
     pub fn new_array(&self) -> Result<Value<'rt>, Value<'rt>> {
         self.try_catch(|| unsafe { Value::from_raw(self.rt, JS_NewArray(self.ptr.as_ptr())) })
     }
@@ -1079,7 +2478,64 @@ impl<'rt> Context<'rt> {
     pub fn is_array(&self, value: &Value) -> bool {
         self.enforce_value_in_same_runtime(value);
 
-        unsafe { JS_IsArray(value.as_raw()) }
+        // JS_IsArray stops at proxies, follow them like Array.isArray does
+        let mut target = value.clone();
+        loop {
+            if unsafe { JS_IsArray(target.as_raw()) } {
+                return true;
+            }
+            if !unsafe { JS_IsProxy(target.as_raw()) } {
+                return false;
+            }
+            // a revoked proxy has no target left to inspect
+            target = match self
+                .try_catch(|| unsafe { Value::from_raw(self.rt, JS_GetProxyTarget(self.ptr.as_ptr(), target.as_raw())) })
+            {
+                Ok(next) => next,
+                Err(_) => return false,
+            };
+        }
+    }
+
+    pub fn array_to_vec(&self, value: &Value) -> Result<Vec<Value<'rt>>, Value<'rt>> {
+        self.check_array(value)?;
+
+        (0..self.get_length(value)?)
+            .map(|index| self.get_property_uint32(value, index as u32))
+            .collect()
+    }
+
+    pub fn array_slice(&self, arr: &Value, start: i64, end: Option<i64>) -> Result<Value<'rt>, Value<'rt>> {
+        self.check_array(arr)?;
+
+        let slice = self.new_atom("slice")?;
+        let start = self.new_number(start as f64);
+
+        match end {
+            Some(end) => self.invoke(arr, &slice, &[start, self.new_number(end as f64)]),
+            None => self.invoke(arr, &slice, &[start]),
+        }
+    }
+
+    pub fn array_concat(&self, a: &Value, b: &Value) -> Result<Value<'rt>, Value<'rt>> {
+        self.check_array(a)?;
+        self.enforce_value_in_same_runtime(b);
+
+        let concat = self.new_atom("concat")?;
+
+        self.invoke(a, &concat, std::slice::from_ref(b))
+    }
+
+    fn check_array(&self, value: &Value) -> Result<(), Value<'rt>> {
+        if self.is_array(value) {
+            return Ok(());
+        }
+
+        self.try_catch(|| unsafe {
+            JS_ThrowTypeError(self.ptr.as_ptr(), c"not an array".as_ptr());
+
+            Err(Exception)
+        })
     }
 
     pub fn get_length(&self, value: &Value) -> Result<i64, Value<'rt>> {
@@ -1092,6 +2548,7 @@ impl<'rt> Context<'rt> {
         })
     }
 
+    // shrinking an array deletes every element at or above `length`
     pub fn set_length(&self, value: &Value, length: i64) -> Result<(), Value<'rt>> {
         self.enforce_value_in_same_runtime(value);
 
@@ -1101,6 +2558,16 @@ impl<'rt> Context<'rt> {
         })
     }
 
+    pub fn truncate_array(&self, arr: &Value, len: i64) -> Result<(), Value<'rt>> {
+        self.check_array(arr)?;
+
+        if len < self.get_length(arr)? {
+            self.set_length(arr, len)
+        } else {
+            Ok(())
+        }
+    }
+
     pub fn is_regexp(&self, value: &Value) -> bool {
         self.enforce_value_in_same_runtime(value);
 
@@ -1113,6 +2580,46 @@ impl<'rt> Context<'rt> {
         unsafe { JS_IsMap(value.as_raw()) }
     }
 
+    pub fn new_weak_map(&self) -> Result<Value<'rt>, Value<'rt>> {
+        let ctor = self.get_property_str(&self.get_global_object(), "WeakMap")?;
+
+        self.call_constructor(&ctor, None, &[])
+    }
+
+    pub fn new_weak_set(&self) -> Result<Value<'rt>, Value<'rt>> {
+        let ctor = self.get_property_str(&self.get_global_object(), "WeakSet")?;
+
+        self.call_constructor(&ctor, None, &[])
+    }
+
+    pub fn weak_map_set(&self, map: &Value, key: &Value, value: Value) -> Result<(), Value<'rt>> {
+        self.invoke(map, &self.new_atom("set")?, &[key.clone(), value])?;
+
+        Ok(())
+    }
+
+    pub fn weak_map_get(&self, map: &Value, key: &Value) -> Result<Value<'rt>, Value<'rt>> {
+        self.invoke(map, &self.new_atom("get")?, std::slice::from_ref(key))
+    }
+
+    pub fn weak_map_has(&self, map: &Value, key: &Value) -> Result<bool, Value<'rt>> {
+        let ret = self.invoke(map, &self.new_atom("has")?, std::slice::from_ref(key))?;
+
+        self.to_bool(&ret)
+    }
+
+    pub fn weak_set_add(&self, set: &Value, value: &Value) -> Result<(), Value<'rt>> {
+        self.invoke(set, &self.new_atom("add")?, std::slice::from_ref(value))?;
+
+        Ok(())
+    }
+
+    pub fn weak_set_has(&self, set: &Value, value: &Value) -> Result<bool, Value<'rt>> {
+        let ret = self.invoke(set, &self.new_atom("has")?, std::slice::from_ref(value))?;
+
+        self.to_bool(&ret)
+    }
+
     pub fn get_property(&self, obj: &Value, prop: &Atom) -> Result<Value<'rt>, Value<'rt>> {
         self.enforce_value_in_same_runtime(obj);
         self.enforce_atom_in_same_runtime(prop);
@@ -1123,17 +2630,47 @@ impl<'rt> Context<'rt> {
         })
     }
 
+    pub fn get_property_as<T>(&self, obj: &Value, prop: &Atom) -> Result<T, Value<'rt>>
+    where
+        T: for<'v> TryFrom<&'v Value<'rt>>,
+    {
+        let value = self.get_property(obj, prop)?;
+
+        T::try_from(&value).or_else(|_| {
+            self.try_catch(|| unsafe {
+                JS_ThrowTypeError(self.ptr.as_ptr(), c"unexpected property value type".as_ptr());
+
+                Err(Exception)
+            })
+        })
+    }
+
     pub fn get_property_str(&self, obj: &Value, prop: impl AsRef<str>) -> Result<Value<'rt>, Value<'rt>> {
         self.enforce_value_in_same_runtime(obj);
 
         self.try_catch(|| unsafe {
-            let prop = self.new_c_string::<64>(prop)?;
+            let prop = self.new_c_string::<PROP_NAME_TINY_CAP>(prop)?;
 
             let value = JS_GetPropertyStr(self.ptr.as_ptr(), obj.as_raw(), prop.as_ptr());
             Value::from_raw(self.rt, value)
         })
     }
 
+    pub fn get_path(&self, root: &Value, path: &str) -> Result<Value<'rt>, Value<'rt>> {
+        let mut current: Option<Value<'rt>> = None;
+
+        for prop in path.split('.') {
+            let obj = current.as_ref().unwrap_or(root);
+            if matches!(obj, Value::Null | Value::Undefined) {
+                return Ok(Value::Undefined);
+            }
+
+            current = Some(self.get_property_str(obj, prop)?);
+        }
+
+        Ok(current.unwrap())
+    }
+
     pub fn get_property_uint32(&self, obj: &Value, prop: u32) -> Result<Value<'rt>, Value<'rt>> {
         self.enforce_value_in_same_runtime(obj);
 
@@ -1158,7 +2695,7 @@ impl<'rt> Context<'rt> {
         self.enforce_value_in_same_runtime(&value);
 
         self.try_catch(|| unsafe {
-            let prop = self.new_c_string::<64>(prop)?;
+            let prop = self.new_c_string::<PROP_NAME_TINY_CAP>(prop)?;
 
             let ret = JS_SetPropertyStr(self.ptr.as_ptr(), obj.as_raw(), prop.as_ptr(), value.into_raw());
             if ret < 0 { Err(Exception) } else { Ok(()) }
@@ -1241,6 +2778,94 @@ impl<'rt> Context<'rt> {
         })
     }
 
+    pub fn lockdown(&self) -> Result<(), Value<'rt>> {
+        const INTRINSICS: &[&str] = &[
+            "Object",
+            "Function",
+            "Array",
+            "Number",
+            "Boolean",
+            "String",
+            "Symbol",
+            "BigInt",
+            "Math",
+            "JSON",
+            "Reflect",
+            "Date",
+            "RegExp",
+            "Error",
+            "EvalError",
+            "RangeError",
+            "ReferenceError",
+            "SyntaxError",
+            "TypeError",
+            "URIError",
+            "AggregateError",
+            "InternalError",
+            "Map",
+            "Set",
+            "WeakMap",
+            "WeakSet",
+            "WeakRef",
+            "FinalizationRegistry",
+            "Promise",
+            "Proxy",
+            "Iterator",
+            "ArrayBuffer",
+            "SharedArrayBuffer",
+            "DataView",
+            "Atomics",
+            "Int8Array",
+            "Uint8Array",
+            "Uint8ClampedArray",
+            "Int16Array",
+            "Uint16Array",
+            "Int32Array",
+            "Uint32Array",
+            "BigInt64Array",
+            "BigUint64Array",
+            "Float16Array",
+            "Float32Array",
+            "Float64Array",
+        ];
+
+        let global = self.get_global_object();
+
+        let mut pending = Vec::new();
+        for name in INTRINSICS {
+            let value = self.get_property_str(&global, name)?;
+            if matches!(value, Value::Object(_)) {
+                pending.push(value);
+            }
+        }
+
+        // deep freeze everything reachable through own properties and prototypes,
+        // already non-extensible objects are treated as visited
+        while let Some(obj) = pending.pop() {
+            if !self.is_extensible(&obj)? {
+                continue;
+            }
+
+            self.freeze_object(&obj)?;
+
+            let proto = self.get_prototype(&obj)?;
+            if matches!(proto, Value::Object(_)) {
+                pending.push(proto);
+            }
+
+            for own in self.get_own_property_atoms(&obj, GetOwnAtomFlags::STRING_MASK | GetOwnAtomFlags::SYMBOL_MASK)? {
+                let desc = self.get_own_property(&obj, &own.atom)?;
+                for value in [desc.value, desc.getter, desc.setter] {
+                    if matches!(value, Value::Object(_)) {
+                        pending.push(value);
+                    }
+                }
+            }
+        }
+
+        Ok(())
+    }
+
     pub fn get_prototype(&self, value: &Value) -> Result<Value<'rt>, Value<'rt>> {
         self.enforce_value_in_same_runtime(value);
 
@@ -1260,7 +2885,49 @@ impl<'rt> Context<'rt> {
         })
     }
 
-    pub fn get_own_property_atoms(&self, obj: &Value, flags: GetOwnAtomFlags) -> Result<Vec<OwnAtom<'rt>>, Value<'rt>> {
+    pub fn get_own_property_atoms(&self, obj: &Value, flags: GetOwnAtomFlags) -> Result<Vec<OwnAtom<'rt>>, Value<'rt>> {
+        self.enforce_value_in_same_runtime(obj);
+
+        self.try_catch(|| unsafe {
+            let mut ptr: *mut rquickjs_sys::JSPropertyEnum = std::ptr::null_mut();
+            let mut length = 0;
+
+            let ret = JS_GetOwnPropertyNames(self.ptr.as_ptr(), &mut ptr, &mut length, obj.as_raw(), flags.bits() as _);
+            if ret < 0 {
+                Err(Exception)
+            } else {
+                let mut atoms = Vec::with_capacity(length as usize);
+                for i in 0..length {
+                    let current = &(*ptr.offset(i as isize));
+                    atoms.push(OwnAtom {
+                        atom: Atom::from_raw(self.rt, JS_DupAtom(self.ptr.as_ptr(), current.atom)),
+                        is_enumerable: current.is_enumerable,
+                    });
+                }
+                JS_FreePropertyEnum(self.ptr.as_ptr(), ptr, length);
+                Ok(atoms)
+            }
+        })
+    }
+
+    pub fn own_key_count(&self, obj: &Value, flags: GetOwnAtomFlags) -> Result<usize, Value<'rt>> {
+        self.enforce_value_in_same_runtime(obj);
+
+        self.try_catch(|| unsafe {
+            let mut ptr: *mut rquickjs_sys::JSPropertyEnum = std::ptr::null_mut();
+            let mut length = 0;
+
+            let ret = JS_GetOwnPropertyNames(self.ptr.as_ptr(), &mut ptr, &mut length, obj.as_raw(), flags.bits() as _);
+            if ret < 0 {
+                Err(Exception)
+            } else {
+                JS_FreePropertyEnum(self.ptr.as_ptr(), ptr, length);
+                Ok(length as usize)
+            }
+        })
+    }
+
+    pub fn own_atoms_iter(&self, obj: &Value, flags: GetOwnAtomFlags) -> Result<OwnAtomIter<'rt>, Value<'rt>> {
         self.enforce_value_in_same_runtime(obj);
 
         self.try_catch(|| unsafe {
@@ -1271,16 +2938,12 @@ impl<'rt> Context<'rt> {
             if ret < 0 {
                 Err(Exception)
             } else {
-                let mut atoms = Vec::with_capacity(length as usize);
-                for i in 0..length {
-                    let current = &(*ptr.offset(i as isize));
-                    atoms.push(OwnAtom {
-                        atom: Atom::from_raw(self.rt, JS_DupAtom(self.ptr.as_ptr(), current.atom)),
-                        is_enumerable: current.is_enumerable,
-                    });
-                }
-                JS_FreePropertyEnum(self.ptr.as_ptr(), ptr, length);
-                Ok(atoms)
+                Ok(OwnAtomIter {
+                    rt: self.rt,
+                    ptr,
+                    length,
+                    index: 0,
+                })
             }
         })
     }
@@ -1332,6 +2995,14 @@ impl<'rt> Context<'rt> {
         })
     }
 
+    pub fn call_simple(&self, func: &Value, args: &[Value]) -> Result<Value<'rt>, Value<'rt>> {
+        self.call(func, &Value::Undefined, args)
+    }
+
+    pub fn try_call(&self, func: &Value, this: &Value, args: &[Value]) -> Result<Value<'rt>, ThrownValue<'rt>> {
+        self.call(func, this, args).map_err(ThrownValue)
+    }
+
     pub fn invoke(&self, obj: &Value, prop: &Atom, args: &[Value]) -> Result<Value<'rt>, Value<'rt>> {
         self.enforce_value_in_same_runtime(obj);
         self.enforce_atom_in_same_runtime(prop);
@@ -1350,6 +3021,13 @@ impl<'rt> Context<'rt> {
         })
     }
 
+    // a returned promise is settled like eval_function_async does
+    pub fn invoke_and_settle(&self, obj: &Value, method: &str, args: &[Value]) -> Result<Value<'rt>, Value<'rt>> {
+        let ret = self.invoke(obj, &self.new_atom(method)?, args)?;
+
+        self.settle(ret)
+    }
+
     pub fn call_constructor(&self, func: &Value, new_target: Option<&Value>, args: &[Value]) -> Result<Value<'rt>, Value<'rt>> {
         self.enforce_value_in_same_runtime(func);
 
@@ -1375,6 +3053,18 @@ impl<'rt> Context<'rt> {
         unsafe { Value::from_raw(self.rt, JS_GetGlobalObject(self.ptr.as_ptr())).unwrap() }
     }
 
+    pub fn global(&self) -> &Value<'rt> {
+        self.global.get_or_init(|| unsafe {
+            let global = JS_GetGlobalObject(self.ptr.as_ptr());
+
+            // drop our reference right away, the context holds the global object until it is freed
+            JS_FreeValueRT(self.rt.as_raw().as_ptr(), global);
+
+            Value::from_raw(self.rt, global).unwrap()
+        })
+    }
+
+    // honors Symbol.hasInstance
     pub fn is_instance_of(&self, value: &Value, proto: &Value) -> Result<bool, Value<'rt>> {
         unsafe {
             self.try_catch(|| {
@@ -1384,6 +3074,55 @@ impl<'rt> Context<'rt> {
         }
     }
 
+    pub fn instance_of(&self, value: &Value, ctor: &Value) -> Result<bool, Value<'rt>> {
+        if !matches!(ctor, Value::Object(_)) {
+            return self.try_catch(|| unsafe {
+                JS_ThrowTypeError(self.ptr.as_ptr(), c"invalid 'instanceof' right operand".as_ptr());
+
+                Err(Exception)
+            });
+        }
+
+        let has_instance = self.has_instance_atom();
+
+        let method = self.get_property(ctor, &has_instance)?;
+        if matches!(method, Value::Undefined | Value::Null) {
+            if !self.is_function(ctor) {
+                return self.try_catch(|| unsafe {
+                    JS_ThrowTypeError(self.ptr.as_ptr(), c"invalid 'instanceof' right operand".as_ptr());
+
+                    Err(Exception)
+                });
+            }
+
+            return self.ordinary_has_instance(ctor, value);
+        }
+
+        let ret = self.call(&method, ctor, std::slice::from_ref(value))?;
+
+        self.to_bool(&ret)
+    }
+
+    pub fn ordinary_has_instance(&self, ctor: &Value, value: &Value) -> Result<bool, Value<'rt>> {
+        let has_instance = self.has_instance_atom();
+
+        // Function.prototype[Symbol.hasInstance] is OrdinaryHasInstance and can't be overridden
+        let ordinary = self.get_property(&self.get_function_proto(), &has_instance)?;
+        let ret = self.call(&ordinary, ctor, std::slice::from_ref(value))?;
+
+        self.to_bool(&ret)
+    }
+
+    fn has_instance_atom(&self) -> Atom<'rt> {
+        // well-known atom, scripts cannot shadow it like globalThis.Symbol
+        unsafe {
+            Atom::from_raw(
+                self.rt,
+                JS_DupAtom(self.ptr.as_ptr(), rquickjs_sys::JS_ATOM_Symbol_hasInstance as _),
+            )
+        }
+    }
+
     pub fn define_property(
         &self,
         this_obj: &Value,
@@ -1435,6 +3174,33 @@ impl<'rt> Context<'rt> {
         })
     }
 
+    pub fn define_property_value_key(
+        &self,
+        this_obj: &Value,
+        key: &Value,
+        value: Value,
+        flags: PropertyDescriptorFlags,
+    ) -> Result<bool, Value<'rt>> {
+        let prop = self.value_to_atom(key)?;
+
+        self.define_property_value(this_obj, &prop, value, flags)
+    }
+
+    pub fn define_constant(&self, obj: &Value, name: &str, value: Value) -> Result<(), Value<'rt>> {
+        self.define_property_value_str(
+            obj,
+            name,
+            value,
+            PropertyDescriptorFlags::ENUMERABLE | PropertyDescriptorFlags::THROW,
+        )?;
+
+        Ok(())
+    }
+
+    pub fn set_function_name(&self, func: &Value, name: &str) -> Result<bool, Value<'rt>> {
+        self.define_property_value_str(func, "name", self.new_string(name)?, PropertyDescriptorFlags::CONFIGURABLE)
+    }
+
     pub fn define_property_value_str(
         &self,
         this_obj: &Value,
@@ -1446,7 +3212,7 @@ impl<'rt> Context<'rt> {
         self.enforce_value_in_same_runtime(&value);
 
         self.try_catch(|| unsafe {
-            let prop = self.new_c_string::<16>(prop)?;
+            let prop = self.new_c_string::<PROP_NAME_TINY_CAP>(prop)?;
             let ret = JS_DefinePropertyValueStr(
                 self.ptr.as_ptr(),
                 this_obj.as_raw(),
@@ -1480,6 +3246,20 @@ impl<'rt> Context<'rt> {
         })
     }
 
+    pub fn define_property_value_int64(
+        &self,
+        this_obj: &Value,
+        prop: i64,
+        value: Value,
+        flags: PropertyDescriptorFlags,
+    ) -> Result<bool, Value<'rt>> {
+        match u32::try_from(prop) {
+            Ok(prop) => self.define_property_value_uint32(this_obj, prop, value, flags),
+            // same key JS_SetPropertyInt64 uses past the array index range
+            Err(_) => self.define_property_value(this_obj, &self.new_atom(prop.to_string())?, value, flags),
+        }
+    }
+
     pub fn define_property_getset(
         &self,
         this_obj: &Value,
@@ -1510,6 +3290,55 @@ impl<'rt> Context<'rt> {
         unsafe { JS_IsPromise(value.as_raw()) }
     }
 
+    // a promise still pending after draining the job queue is reported as an error
+    pub fn resolve_promise(&self, promise: &Value) -> Result<Value<'rt>, Value<'rt>> {
+        self.enforce_value_in_same_runtime(promise);
+
+        if !self.is_promise(promise) {
+            return self.try_catch(|| unsafe {
+                JS_ThrowTypeError(self.ptr.as_ptr(), c"not a promise".as_ptr());
+
+                Err(Exception)
+            });
+        }
+
+        self.rt.drain_pending_jobs(usize::MAX);
+
+        match self.get_promise_state(promise) {
+            Ok(PromiseState::Fulfilled) => Ok(self.get_promise_result(promise)),
+            Ok(PromiseState::Rejected) => Err(self.get_promise_result(promise)),
+            Ok(PromiseState::Pending) => Err(self.throw_error("promise still pending after executing jobs")),
+            Err(NotAPromise) => unreachable!("checked above"),
+        }
+    }
+
+    pub fn promise_then<F, R>(&self, promise: &Value, on_fulfilled: F, on_rejected: R) -> Result<Value<'rt>, Value<'rt>>
+    where
+        F: for<'r> Fn(&Context<'r>, &Value) -> Result<Value<'r>, Value<'r>> + Send + 'static,
+        R: for<'r> Fn(&Context<'r>, &Value) -> Result<Value<'r>, Value<'r>> + Send + 'static,
+    {
+        if !self.is_promise(promise) {
+            return self.try_catch(|| unsafe {
+                JS_ThrowTypeError(self.ptr.as_ptr(), c"not a promise".as_ptr());
+
+                Err(Exception)
+            });
+        }
+
+        let on_fulfilled = self.new_object_class(
+            NativeFunction::new(move |ctx, _, _, args, _| on_fulfilled(ctx, args.first().unwrap_or(&Value::Undefined))),
+            None,
+        )?;
+        let on_rejected = self.new_object_class(
+            NativeFunction::new(move |ctx, _, _, args, _| on_rejected(ctx, args.first().unwrap_or(&Value::Undefined))),
+            None,
+        )?;
+
+        let then = self.new_atom("then")?;
+
+        self.invoke(promise, &then, &[on_fulfilled, on_rejected])
+    }
+
     pub fn new_promise_capability(&self) -> Result<(Value<'rt>, (Value<'rt>, Value<'rt>)), Value<'rt>> {
         self.try_catch(|| unsafe {
             let mut resolving_funcs = [rquickjs_sys::JS_UNDEFINED, rquickjs_sys::JS_UNDEFINED];
@@ -1524,6 +3353,33 @@ impl<'rt> Context<'rt> {
         })
     }
 
+    pub fn new_promise_capability_global(&self) -> Result<(GlobalValue, GlobalValue, GlobalValue), Value<'rt>> {
+        let (promise, (resolve, reject)) = self.new_promise_capability()?;
+
+        Ok((
+            self.rt.new_global_value(&promise).unwrap(),
+            self.rt.new_global_value(&resolve).unwrap(),
+            self.rt.new_global_value(&reject).unwrap(),
+        ))
+    }
+
+    pub fn resolved_promise(&self, value: Value) -> Result<Value<'rt>, Value<'rt>> {
+        self.settled_promise("resolve", value)
+    }
+
+    pub fn rejected_promise(&self, reason: Value) -> Result<Value<'rt>, Value<'rt>> {
+        self.settled_promise("reject", reason)
+    }
+
+    fn settled_promise(&self, method: &str, value: Value) -> Result<Value<'rt>, Value<'rt>> {
+        self.enforce_value_in_same_runtime(&value);
+
+        let promise = self.get_property_str(&self.get_global_object(), "Promise")?;
+        let method = self.new_atom(method)?;
+
+        self.invoke(&promise, &method, &[value])
+    }
+
     pub fn get_promise_state(&self, promise: &Value) -> Result<PromiseState, NotAPromise> {
         unsafe {
             let ret = JS_PromiseState(self.ptr.as_ptr(), promise.as_raw());
@@ -1546,13 +3402,28 @@ impl<'rt> Context<'rt> {
     pub fn new_symbol(&self, description: &str, is_global: bool) -> Result<Value<'rt>, Value<'rt>> {
         unsafe {
             self.try_catch(|| {
-                let description = self.new_c_string::<16>(description)?;
+                let description = self.new_c_string::<PROP_NAME_TINY_CAP>(description)?;
                 let value = JS_NewSymbol(self.ptr.as_ptr(), description.as_ptr(), is_global);
                 Value::from_raw(self.rt, value)
             })
         }
     }
 
+    pub fn symbol_description(&self, sym: &Value) -> Result<Option<std::string::String>, Value<'rt>> {
+        if !matches!(sym, Value::Symbol(_)) {
+            return self.try_catch(|| unsafe {
+                JS_ThrowTypeError(self.ptr.as_ptr(), c"not a symbol".as_ptr());
+
+                Err(Exception)
+            });
+        }
+
+        match self.get_property_str(sym, "description")? {
+            Value::Undefined => Ok(None),
+            description => Ok(Some(self.get_string_lossy(&description)?)),
+        }
+    }
+
     pub fn new_date(&self, epoch_ms: f64) -> Result<Value<'rt>, Value<'rt>> {
         unsafe {
             self.try_catch(|| {
@@ -1578,7 +3449,7 @@ impl<'rt> Context<'rt> {
         ) -> rquickjs_sys::JSValue,
         data: B,
         shared: bool,
-    ) -> Result<Value<'rt>, Value<'rt>> {
+    ) -> Result<Value<'rt>, BufferAllocError<'rt>> {
         self.try_catch(move || unsafe {
             extern "C" fn free_data<B>(
                 _: *mut rquickjs_sys::JSRuntime,
@@ -1609,6 +3480,21 @@ impl<'rt> Context<'rt> {
                 }
             }
         })
+        .map_err(|err| self.buffer_alloc_error(err))
+    }
+
+    fn buffer_alloc_error(&self, err: Value<'rt>) -> BufferAllocError<'rt> {
+        // JS_ThrowOutOfMemory throws a plain InternalError, the message is the only marker
+        let out_of_memory = self.is_error(&err)
+            && self
+                .get_property_str(&err, "message")
+                .is_ok_and(|m| self.get_string(&m).is_ok_and(|m| m.as_bytes() == b"out of memory"));
+
+        if out_of_memory {
+            BufferAllocError::OutOfMemory(err)
+        } else {
+            BufferAllocError::Exception(err)
+        }
     }
 
     fn new_buffer_copy_from_slice(
@@ -1619,18 +3505,37 @@ impl<'rt> Context<'rt> {
             len: rquickjs_sys::size_t,
         ) -> rquickjs_sys::JSValue,
         data: &[u8],
-    ) -> Result<Value<'rt>, Value<'rt>> {
+    ) -> Result<Value<'rt>, BufferAllocError<'rt>> {
         self.try_catch(move || unsafe {
             let ret = func(self.ptr.as_ptr(), data.as_ptr(), data.len() as _);
             Value::from_raw(self.rt, ret)
         })
+        .map_err(|err| self.buffer_alloc_error(err))
     }
 
-    pub fn new_array_buffer<B: AsMut<[u8]> + Sized>(&self, data: B, shared: bool) -> Result<Value<'rt>, Value<'rt>> {
+    pub fn new_array_buffer<B: AsMut<[u8]> + Sized>(&self, data: B, shared: bool) -> Result<Value<'rt>, BufferAllocError<'rt>> {
         self.new_buffer_from_data(JS_NewArrayBuffer, data, shared)
     }
 
-    pub fn new_array_buffer_copy(&self, data: &[u8]) -> Result<Value<'rt>, Value<'rt>> {
+    pub fn new_array_buffer_borrowed(&self, data: &'static mut [u8]) -> Result<Value<'rt>, BufferAllocError<'rt>> {
+        extern "C" fn no_free(_: *mut rquickjs_sys::JSRuntime, _: *mut rquickjs_sys::c_void, _: *mut rquickjs_sys::c_void) {}
+
+        self.try_catch(|| unsafe {
+            let ret = JS_NewArrayBuffer(
+                self.ptr.as_ptr(),
+                data.as_mut_ptr(),
+                data.len() as _,
+                Some(no_free),
+                std::ptr::null_mut(),
+                false,
+            );
+
+            Value::from_raw(self.rt, ret)
+        })
+        .map_err(|err| self.buffer_alloc_error(err))
+    }
+
+    pub fn new_array_buffer_copy(&self, data: &[u8]) -> Result<Value<'rt>, BufferAllocError<'rt>> {
         self.new_buffer_copy_from_slice(JS_NewArrayBufferCopy, data)
     }
 
@@ -1659,6 +3564,17 @@ impl<'rt> Context<'rt> {
         })
     }
 
+    pub fn array_buffer_as_ref<R>(&self, value: &Value, f: impl FnOnce(&[u8]) -> R) -> Result<R, Value<'rt>> {
+        // `f` may reach the engine and detach or resize the buffer, so it reads a snapshot
+        let data = self.array_buffer_to_vec(value)?;
+
+        Ok(f(&data))
+    }
+
+    pub fn array_buffer_to_vec(&self, value: &Value) -> Result<Vec<u8>, Value<'rt>> {
+        unsafe { self.get_array_buffer(value).map(|data| data.to_vec()) }
+    }
+
     pub fn is_array_buffer(&self, value: &Value) -> bool {
         self.enforce_value_in_same_runtime(value);
 
@@ -1708,6 +3624,34 @@ impl<'rt> Context<'rt> {
         })
     }
 
+    pub fn classify(&self, value: &Value) -> JsKind {
+        self.enforce_value_in_same_runtime(value);
+
+        match value {
+            Value::Undefined => JsKind::Undefined,
+            Value::Null => JsKind::Null,
+            Value::Bool(_) => JsKind::Bool,
+            Value::Int32(_) | Value::Float64(_) => JsKind::Number,
+            Value::BigInt(_) | Value::ShortBigInt(_) => JsKind::BigInt,
+            Value::String(_) => JsKind::String,
+            Value::Symbol(_) => JsKind::Symbol,
+            Value::Module(_) => JsKind::Module,
+            Value::FunctionByteCode(_) => JsKind::FunctionByteCode,
+            Value::Uninitialized | Value::CatchOffset(_) => JsKind::Internal,
+            // builtin class ids are not exported by the bindings, ask the engine
+            Value::Object(_) if self.is_array(value) => JsKind::Array,
+            Value::Object(_) if self.is_typed_array(value) => JsKind::TypedArray,
+            Value::Object(_) if self.is_function(value) => JsKind::Function,
+            Value::Object(_) => JsKind::Object,
+        }
+    }
+
+    pub fn is_typed_array(&self, value: &Value) -> bool {
+        self.enforce_value_in_same_runtime(value);
+
+        unsafe { JS_GetTypedArrayType(value.as_raw()) >= 0 }
+    }
+
     pub fn get_typed_array_type(&self, value: &Value) -> Result<TypedArrayType, Value<'rt>> {
         self.enforce_value_in_same_runtime(value);
 
@@ -1721,11 +3665,38 @@ impl<'rt> Context<'rt> {
         })
     }
 
-    pub fn new_uint8_array_buffer<B: AsMut<[u8]> + Sized>(&self, data: B, shared: bool) -> Result<Value<'rt>, Value<'rt>> {
+    // out of range indices yield NaN, bigint arrays throw
+    pub fn typed_array_get_f64(&self, value: &Value, index: usize) -> Result<f64, Value<'rt>> {
+        if !self.is_typed_array(value) {
+            return self.try_catch(|| unsafe {
+                JS_ThrowTypeError(self.ptr.as_ptr(), c"not a typed array".as_ptr());
+
+                Err(Exception)
+            });
+        }
+
+        let element = match u32::try_from(index) {
+            Ok(index) => self.get_property_uint32(value, index)?,
+            Err(_) => Value::Undefined,
+        };
+
+        self.to_float64(&element)
+    }
+
+    // no copy, `data` is dropped once the array is collected
+    pub fn new_uint8_array_buffer<B: AsMut<[u8]> + Sized>(
+        &self,
+        data: B,
+        shared: bool,
+    ) -> Result<Value<'rt>, BufferAllocError<'rt>> {
         self.new_buffer_from_data(JS_NewUint8Array, data, shared)
     }
 
-    pub fn new_uint8_array_buffer_copy(&self, data: &[u8]) -> Result<Value<'rt>, Value<'rt>> {
+    pub fn new_uint8_array_from_vec(&self, data: Vec<u8>) -> Result<Value<'rt>, BufferAllocError<'rt>> {
+        self.new_uint8_array_buffer(data, false)
+    }
+
+    pub fn new_uint8_array_buffer_copy(&self, data: &[u8]) -> Result<Value<'rt>, BufferAllocError<'rt>> {
         self.new_buffer_copy_from_slice(JS_NewUint8ArrayCopy, data)
     }
 
@@ -1740,6 +3711,14 @@ impl<'rt> Context<'rt> {
         }
     }
 
+    pub fn parse_json_ext(&self, json: &str, filename: &str, allow_extensions: bool) -> Result<Value<'rt>, Value<'rt>> {
+        if allow_extensions {
+            self.parse_json(&strip_json_extensions(json), filename)
+        } else {
+            self.parse_json(json, filename)
+        }
+    }
+
     pub fn json_stringify(&self, value: &Value, replacer: &Value, space: &Value) -> Result<Value<'rt>, Value<'rt>> {
         unsafe {
             self.try_catch(|| {
@@ -1749,6 +3728,130 @@ impl<'rt> Context<'rt> {
         }
     }
 
+    pub fn stringify_to_writer(&self, value: &Value, w: &mut dyn std::io::Write) -> Result<(), Value<'rt>> {
+        self.enforce_value_in_same_runtime(value);
+
+        // like JSON.stringify, the root is serialized as the "" property of a wrapper object
+        let wrapper = self.new_object(None)?;
+        self.set_property_str(&wrapper, "", value.clone())?;
+        let value = self.get_property_str(&wrapper, "")?;
+
+        // boxed primitives serialize as their primitive value (SerializeJSONProperty step 4), the engine unwraps them
+        let primitives = [
+            Value::Int32(0),
+            self.new_string("")?,
+            Value::Bool(false),
+            Value::ShortBigInt(0),
+        ];
+        let mut boxed_classes = [0; 4];
+        for (class_id, primitive) in boxed_classes.iter_mut().zip(&primitives) {
+            *class_id = unsafe { JS_GetClassID(self.to_object(primitive)?.as_raw()) };
+        }
+
+        let mut stack = HashSet::new();
+
+        match self.to_json_value(&self.new_string("")?, &value)? {
+            Some(value) => self.write_json_value(&value, w, &boxed_classes, &mut stack),
+            None => Ok(()),
+        }
+    }
+
+    // apply toJSON and filter out values skipped by JSON.stringify
+    fn to_json_value(&self, key: &Value, value: &Value<'rt>) -> Result<Option<Value<'rt>>, Value<'rt>> {
+        let value = match value {
+            Value::Object(_) | Value::BigInt(_) | Value::ShortBigInt(_) => {
+                let to_json = self.get_property_str(value, "toJSON")?;
+                if self.is_function(&to_json) {
+                    self.call(&to_json, value, std::slice::from_ref(key))?
+                } else {
+                    value.clone()
+                }
+            }
+            _ => value.clone(),
+        };
+
+        match &value {
+            Value::Undefined | Value::Symbol(_) => Ok(None),
+            Value::Object(_) if self.is_function(&value) => Ok(None),
+            _ => Ok(Some(value)),
+        }
+    }
+
+    fn write_json_value(
+        &self,
+        value: &Value<'rt>,
+        w: &mut dyn std::io::Write,
+        boxed_classes: &[rquickjs_sys::JSClassID],
+        stack: &mut HashSet<*mut std::ffi::c_void>,
+    ) -> Result<(), Value<'rt>> {
+        let write = |w: &mut dyn std::io::Write, data: &[u8]| -> Result<(), Value<'rt>> {
+            w.write_all(data).map_err(|err| self.throw_error(err.to_string()))
+        };
+
+        let is_boxed = boxed_classes.contains(&unsafe { JS_GetClassID(value.as_raw()) });
+        if !matches!(value, Value::Object(_)) || is_boxed {
+            let json = self.json_stringify(value, &Value::Undefined, &Value::Undefined)?;
+            return write(w, self.get_string(&json)?.to_string_lossy().as_bytes());
+        }
+
+        // objects on the current path, keyed by identity
+        let object = unsafe { value.as_raw().u.ptr };
+        if stack.contains(&object) {
+            return Err(self.throw_type_error("circular structure in stringify"));
+        }
+        // the walker recurses on the native stack, refuse nesting before it overflows
+        if stack.len() >= JSON_WRITER_MAX_DEPTH {
+            return Err(self.throw_range_error("maximum nesting depth exceeded in stringify"));
+        }
+        stack.insert(object);
+
+        if self.is_array(value) {
+            write(w, b"[")?;
+
+            let length = self.get_length(value)?;
+            for index in 0..length {
+                if index > 0 {
+                    write(w, b",")?;
+                }
+
+                let element = self.get_property_uint32(value, index as u32)?;
+                match self.to_json_value(&self.new_string(index.to_string())?, &element)? {
+                    Some(element) => self.write_json_value(&element, w, boxed_classes, stack)?,
+                    None => write(w, b"null")?,
+                }
+            }
+
+            write(w, b"]")?;
+        } else {
+            write(w, b"{")?;
+
+            let mut first = true;
+            for own in self.get_own_property_atoms(value, GetOwnAtomFlags::STRING_MASK | GetOwnAtomFlags::ENUM_ONLY)? {
+                let key = self.atom_to_string(&own.atom)?;
+                let element = self.get_property(value, &own.atom)?;
+
+                if let Some(element) = self.to_json_value(&key, &element)? {
+                    if !first {
+                        write(w, b",")?;
+                    }
+                    first = false;
+
+                    let key = self.json_stringify(&key, &Value::Undefined, &Value::Undefined)?;
+                    write(w, self.get_string(&key)?.to_string_lossy().as_bytes())?;
+                    write(w, b":")?;
+
+                    self.write_json_value(&element, w, boxed_classes, stack)?;
+                }
+            }
+
+            write(w, b"}")?;
+        }
+
+        stack.remove(&object);
+
+        Ok(())
+    }
+
     pub fn write_object(&self, value: &Value, flags: WriteObjectFlags) -> Result<Vec<u8>, Value<'rt>> {
         unsafe {
             let mut size = 0;
@@ -1772,6 +3875,19 @@ impl<'rt> Context<'rt> {
         })
     }
 
+    pub fn spawn_child_realm(&self) -> Context<'rt> {
+        self.rt.new_context()
+    }
+
+    pub fn transfer_value(&self, value: &Value, target: &Context<'rt>) -> Result<Value<'rt>, Value<'rt>> {
+        self.enforce_value_in_same_runtime(value);
+
+        // structured clone of plain data, functions and native objects are rejected by write_object
+        let data = self.write_object(value, WriteObjectFlags::empty())?;
+
+        target.read_object(&data, ReadObjectFlags::empty())
+    }
+
     pub fn eval_function(&self, func: Value) -> Result<Value<'rt>, Value<'rt>> {
         self.enforce_value_in_same_runtime(&func);
 
@@ -1782,6 +3898,38 @@ impl<'rt> Context<'rt> {
         })
     }
 
+    // a promise still pending after draining the job queue is reported as an error
+    pub fn eval_function_async(&self, func: Value) -> Result<Value<'rt>, Value<'rt>> {
+        let ret = self.eval_function(func)?;
+
+        self.settle(ret)
+    }
+
+    fn settle(&self, ret: Value<'rt>) -> Result<Value<'rt>, Value<'rt>> {
+        if self.is_promise(&ret) {
+            return self.resolve_promise(&ret);
+        }
+
+        self.rt.drain_pending_jobs(usize::MAX);
+
+        Ok(ret)
+    }
+
+    // returns the evaluation promise unsettled
+    pub fn link_and_evaluate_module(&self, module: Value) -> Result<Value<'rt>, Value<'rt>> {
+        if !module.is_module() {
+            return self.try_catch(|| unsafe {
+                JS_ThrowTypeError(self.ptr.as_ptr(), c"not a module".as_ptr());
+
+                Err(Exception)
+            });
+        }
+
+        self.resolve_module(&module)?;
+
+        self.eval_function(module)
+    }
+
     pub fn resolve_module(&self, module: &Value) -> Result<(), Value<'rt>> {
         self.enforce_value_in_same_runtime(module);
 
@@ -1791,6 +3939,7 @@ impl<'rt> Context<'rt> {
         })
     }
 
+    // a panic in `f` is reported as an InternalError by `Runtime::execute_pending_jobs`
     pub fn enqueue_job<F: for<'c, 'r> FnOnce(&'c Context<'r>) + Send + 'static>(&self, f: F) -> Result<(), Value<'rt>> {
         struct FnHolder<F> {
             f: Option<F>,
@@ -1815,6 +3964,7 @@ impl<'rt> Context<'rt> {
                 let ctx = ManuallyDrop::new(Context {
                     rt: &rt,
                     ptr: NonNull::new(ctx).unwrap(),
+                    global: OnceCell::new(),
                 });
                 let arg = ManuallyDrop::new(Value::from_raw(&rt, *argv).unwrap());
 
@@ -1822,7 +3972,15 @@ impl<'rt> Context<'rt> {
                 let fn_holder = &mut *(&raw const *fn_holder).cast_mut();
 
                 let f = fn_holder.f.take().unwrap();
-                f(&ctx);
+                if let Err(payload) = std::panic::catch_unwind(AssertUnwindSafe(|| f(&ctx))) {
+                    let msg = match payload.downcast_ref::<&str>() {
+                        Some(msg) => msg.to_string(),
+                        None => payload.downcast_ref::<std::string::String>().cloned().unwrap_or_default(),
+                    };
+                    let msg = CString::new(format!("job panicked: {}", msg).replace('\0', "")).unwrap();
+
+                    return JS_ThrowInternalError(ctx.ptr.as_ptr(), c"%s".as_ptr(), msg.as_ptr());
+                }
             }
 
             rquickjs_sys::JS_UNDEFINED
@@ -1837,25 +3995,83 @@ impl<'rt> Context<'rt> {
             Ok(())
         }
     }
+
+    pub fn enqueue_js_job(&self, func: Value, args: Vec<Value>) -> Result<(), Value<'rt>> {
+        unsafe extern "C" fn call_js_job(
+            ctx: *mut rquickjs_sys::JSContext,
+            argc: i32,
+            argv: *mut rquickjs_sys::JSValue,
+        ) -> rquickjs_sys::JSValue {
+            assert!(argc >= 1);
+            assert!(!argv.is_null());
+
+            unsafe { JS_Call(ctx, *argv, rquickjs_sys::JS_UNDEFINED, argc - 1, argv.add(1)) }
+        }
+
+        if !self.is_function(&func) {
+            return self.try_catch(|| unsafe {
+                JS_ThrowTypeError(self.ptr.as_ptr(), c"not a function".as_ptr());
+
+                Err(Exception)
+            });
+        }
+
+        // the job queue holds its own references to func and args until executed
+        let values = std::iter::once(func).chain(args).collect::<Vec<_>>();
+        let argv = self.convert_value_to_raw_value::<16>(&values);
+
+        unsafe {
+            let ret = JS_EnqueueJob(
+                self.ptr.as_ptr(),
+                Some(call_js_job),
+                argv.len() as _,
+                argv.as_ptr().cast_mut(),
+            );
+            assert!(ret >= 0, "out of memory");
+
+            Ok(())
+        }
+    }
 }
 
 pub struct JSStr<'v> {
-    ctx: &'v Context<'v>,
+    ctx: &'v NonNull<rquickjs_sys::JSContext>,
     ptr: *const std::ffi::c_char,
     len: usize,
+    // only set when the bytes are not valid UTF-8
+    lossy: Option<Box<str>>,
 }
 
 impl<'v> Drop for JSStr<'v> {
     fn drop(&mut self) {
-        unsafe { JS_FreeCString(self.ctx.ptr.as_ptr(), self.ptr) }
+        unsafe { JS_FreeCString(self.ctx.as_ptr(), self.ptr) }
+    }
+}
+
+impl<'v> JSStr<'v> {
+    // lone surrogates are kept as 3 byte sequences (WTF-8)
+    pub fn as_bytes(&self) -> &[u8] {
+        unsafe { std::slice::from_raw_parts(self.ptr as _, self.len) }
+    }
+
+    pub fn to_str(&self) -> Result<&str, std::str::Utf8Error> {
+        std::str::from_utf8(self.as_bytes())
+    }
+
+    pub fn to_string_lossy(&self) -> std::borrow::Cow<'_, str> {
+        std::string::String::from_utf8_lossy(self.as_bytes())
     }
 }
 
 impl<'v> Deref for JSStr<'v> {
     type Target = str;
 
+    // lone surrogates read as U+FFFD, to_str is the checked path
     fn deref(&self) -> &Self::Target {
-        unsafe { std::str::from_utf8_unchecked(std::slice::from_raw_parts(self.ptr as _, self.len)) }
+        match &self.lossy {
+            Some(lossy) => lossy,
+            None => unsafe { std::str::from_utf8_unchecked(self.as_bytes()) },
+        }
     }
 }
 
@@ -1865,3 +4081,7 @@ pub fn detect_module(s: impl AsRef<str>) -> bool {
         Err(_) => false,
     }
 }
+
+pub fn quickjs_version() -> &'static str {
+    Runtime::version()
+}
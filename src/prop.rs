@@ -1,4 +1,16 @@
-use crate::{CallOptions, Context, NativeFunction, PropertyDescriptorFlags, Value};
+use std::sync::{Arc, Mutex};
+
+use crate::{CallOptions, Class, Context, NativeFunction, PropertyDescriptorFlags, Value};
+
+pub type GetterFn<C> = for<'r> fn(&Context<'r>, &C) -> Result<Value<'r>, Value<'r>>;
+
+pub type NativeFn = for<'r> fn(&Context<'r>, &Value, &Value, &[Value], CallOptions) -> Result<Value<'r>, Value<'r>>;
+
+pub enum NamespaceEntry<'a, 'rt> {
+    Function { name: &'a str, arity: u32, f: NativeFn },
+    Value { name: &'a str, value: Value<'rt> },
+    Getter { name: &'a str, g: NativeFn },
+}
 
 #[derive(Clone)]
 pub struct NativeProperty<
@@ -98,3 +110,136 @@ impl<'rt> NativePropertyExt<'rt> for Context<'rt> {
         self.define_property(obj, &atom, &prop.value, &getter, &setter, flags)
     }
 }
+
+impl<'rt> Context<'rt> {
+    pub fn populate(&self, obj: &Value, entries: &[NamespaceEntry<'_, 'rt>]) -> Result<(), Value<'rt>> {
+        for entry in entries {
+            match entry {
+                NamespaceEntry::Function { name, arity, f } => {
+                    let func = self.new_object_class(NativeFunction::new(*f), None)?;
+                    self.set_function_name(&func, name)?;
+                    self.define_property_value_str(
+                        &func,
+                        "length",
+                        Value::Int32(*arity as i32),
+                        PropertyDescriptorFlags::CONFIGURABLE,
+                    )?;
+
+                    self.define_property_value_str(
+                        obj,
+                        name,
+                        func,
+                        PropertyDescriptorFlags::CONFIGURABLE | PropertyDescriptorFlags::WRITABLE,
+                    )?;
+                }
+                NamespaceEntry::Value { name, value } => {
+                    self.define_property_value_str(
+                        obj,
+                        name,
+                        value.clone(),
+                        PropertyDescriptorFlags::CONFIGURABLE | PropertyDescriptorFlags::WRITABLE,
+                    )?;
+                }
+                NamespaceEntry::Getter { name, g } => {
+                    let atom = self.new_atom(name)?;
+                    let getter = self.new_object_class(NativeFunction::new(*g), None)?;
+
+                    self.define_property(
+                        obj,
+                        &atom,
+                        &Value::Undefined,
+                        &getter,
+                        &Value::Undefined,
+                        PropertyDescriptorFlags::CONFIGURABLE | PropertyDescriptorFlags::HAS_GET,
+                    )?;
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    pub fn define_method<C, F>(&self, proto: &Value, name: &str, f: F) -> Result<bool, Value<'rt>>
+    where
+        C: Class,
+        F: for<'r> Fn(&Context<'r>, &C, &[Value]) -> Result<Value<'r>, Value<'r>> + Send + 'static,
+    {
+        let method = NativeFunction::new(move |ctx, _, this, args, _| match ctx.get_class_opaque::<C>(this) {
+            Some(instance) => f(ctx, instance, args),
+            None => Err(ctx.throw_type_error(format!("{} method called on incompatible receiver", C::NAME))),
+        });
+
+        let method = self.new_object_class(method, None)?;
+        self.set_function_name(&method, name)?;
+
+        self.define_property_value_str(
+            proto,
+            name,
+            method,
+            PropertyDescriptorFlags::CONFIGURABLE | PropertyDescriptorFlags::WRITABLE,
+        )
+    }
+
+    pub fn define_reactive_property<T>(&self, obj: &Value, name: &str, state: Arc<Mutex<T>>) -> Result<bool, Value<'rt>>
+    where
+        T: Clone + Send + 'static + for<'r> Into<Value<'r>> + for<'v, 'r> TryFrom<&'v Value<'r>>,
+    {
+        let getter = {
+            let state = state.clone();
+
+            NativeFunction::new(move |_, _, _, _, _| Ok(state.lock().unwrap_or_else(|err| err.into_inner()).clone().into()))
+        };
+
+        let setter = {
+            let name = name.to_owned();
+
+            NativeFunction::new(
+                move |ctx, _, _, args, _| match T::try_from(args.first().unwrap_or(&Value::Undefined)) {
+                    Ok(value) => {
+                        *state.lock().unwrap_or_else(|err| err.into_inner()) = value;
+
+                        Ok(Value::Undefined)
+                    }
+                    Err(_) => Err(ctx.throw_error(format!("unexpected value type for '{}'", name))),
+                },
+            )
+        };
+
+        self.define_native_property(
+            obj,
+            name,
+            NativeProperty {
+                getter: Some(getter),
+                setter: Some(setter),
+                writable: false,
+                ..Default::default()
+            },
+        )
+    }
+
+    pub fn expose_struct<C: Class>(&self, instance: C, fields: &[(&str, GetterFn<C>)]) -> Result<Value<'rt>, Value<'rt>> {
+        let obj = self.new_object_class(instance, None)?;
+
+        for &(name, getter) in fields {
+            // getters read the opaque on every access, so JS observes later changes of the rust state
+            let getter = NativeFunction::new(move |ctx, _, this, _, _| match ctx.get_class_opaque::<C>(this) {
+                Some(instance) => getter(ctx, instance),
+                None => Err(ctx.throw_type_error(format!("{} getter called on incompatible receiver", C::NAME))),
+            });
+
+            let atom = self.new_atom(name)?;
+            let getter = self.new_object_class(getter, None)?;
+
+            self.define_property(
+                &obj,
+                &atom,
+                &Value::Undefined,
+                &getter,
+                &Value::Undefined,
+                PropertyDescriptorFlags::CONFIGURABLE | PropertyDescriptorFlags::ENUMERABLE | PropertyDescriptorFlags::HAS_GET,
+            )?;
+        }
+
+        Ok(obj)
+    }
+}
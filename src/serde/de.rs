@@ -2,15 +2,16 @@ use serde::{
     Deserialize, Deserializer,
     de::{
         DeserializeSeed, EnumAccess, Error, IntoDeserializer, MapAccess, SeqAccess, Unexpected, VariantAccess, Visitor,
-        value::SeqDeserializer,
+        value::{F64Deserializer, SeqDeserializer},
     },
 };
 
 use crate::{
-    Atom, Context, GetOwnAtomFlags, OwnAtom, Value,
+    Atom, Context, GetOwnAtomFlags, JsKind, OwnAtom, Value,
     serde::{
         error::{collect_path, error_to_string},
         pool::AtomPool,
+        value::{DATE_MARKER, JS_VALUE_TOKEN, capture_value},
     },
 };
 
@@ -77,11 +78,144 @@ impl<'a, 'rt> ValueDeserializer<'a, 'rt> {
         };
 
         match self.ctx.get_string(&s) {
-            Ok(v) => visitor.visit_str(&v).map_err(|err| self.fix_path(err)),
+            Ok(v) => visitor.visit_str(&v.to_string_lossy()).map_err(|err| self.fix_path(err)),
             Err(e) => Err(self.value_to_error(&e)),
         }
     }
 
+    fn deserialize_array<V: Visitor<'rt>>(&self, visitor: V) -> Result<V::Value, super::Error> {
+        struct ArrayAccess<'a, 'rt> {
+            array: &'a ValueDeserializer<'a, 'rt>,
+            index: u32,
+            length: u32,
+        }
+
+        impl<'a, 'rt> SeqAccess<'rt> for ArrayAccess<'a, 'rt> {
+            type Error = super::Error;
+
+            fn next_element_seed<T>(&mut self, seed: T) -> Result<Option<T::Value>, Self::Error>
+            where
+                T: DeserializeSeed<'rt>,
+            {
+                if self.index < self.length {
+                    let index = self
+                        .array
+                        .ctx
+                        .new_atom_uint32(self.index)
+                        .map_err(|err| self.array.value_to_error(&err))?;
+                    let elm = self
+                        .array
+                        .ctx
+                        .get_property(self.array.value, &index)
+                        .map_err(|err| self.array.value_to_error(&err))?;
+
+                    self.index += 1;
+
+                    let deserializer = self.array.derive_child_value(&index, &elm);
+                    seed.deserialize(deserializer.clone())
+                        .map(Some)
+                        .map_err(|err| deserializer.fix_path(err))
+                } else {
+                    Ok(None)
+                }
+            }
+
+            fn size_hint(&self) -> Option<usize> {
+                Some((self.length - self.index) as usize)
+            }
+        }
+
+        visitor
+            .visit_seq(ArrayAccess {
+                array: self,
+                index: 0,
+                length: self.ctx.get_length(&self.value).map_err(|err| self.value_to_error(&err))? as _,
+            })
+            .map_err(|err| self.fix_path(err))
+    }
+
+    fn deserialize_js_map<V: Visitor<'rt>>(&self, visitor: V) -> Result<V::Value, super::Error> {
+        struct JsMapAccess<'a, 'rt> {
+            map: &'a ValueDeserializer<'a, 'rt>,
+            entries: std::vec::IntoIter<(Atom<'rt>, Value<'rt>, Value<'rt>)>,
+            next_value: Option<(Atom<'rt>, Value<'rt>)>,
+        }
+
+        impl<'a, 'rt> MapAccess<'rt> for JsMapAccess<'a, 'rt> {
+            type Error = super::Error;
+
+            fn next_key_seed<K>(&mut self, seed: K) -> Result<Option<K::Value>, Self::Error>
+            where
+                K: DeserializeSeed<'rt>,
+            {
+                if let Some((atom, key, value)) = self.entries.next() {
+                    let deserializer = self.map.derive_child_value(&atom, &key);
+
+                    let ret = seed
+                        .deserialize(deserializer.clone())
+                        .map(Some)
+                        .map_err(|err| deserializer.fix_path(err));
+
+                    self.next_value = Some((atom, value));
+
+                    ret
+                } else {
+                    Ok(None)
+                }
+            }
+
+            fn next_value_seed<V>(&mut self, seed: V) -> Result<V::Value, Self::Error>
+            where
+                V: DeserializeSeed<'rt>,
+            {
+                let (atom, value) = self.next_value.take().expect("call next value before next key");
+                let deserializer = self.map.derive_child_value(&atom, &value);
+
+                seed.deserialize(deserializer.clone())
+                    .map_err(|err| deserializer.fix_path(err))
+            }
+
+            fn size_hint(&self) -> Option<usize> {
+                Some(self.entries.len())
+            }
+        }
+
+        // Array.from(map) yields the [key, value] pairs in insertion order
+        let array = self
+            .ctx
+            .get_property_str(self.ctx.global(), "Array")
+            .and_then(|array| {
+                let from = self.ctx.new_atom("from")?;
+                self.ctx.invoke(&array, &from, &[self.value.clone()])
+            })
+            .map_err(|err| self.value_to_error(&err))?;
+
+        let entries = self
+            .ctx
+            .array_to_vec(&array)
+            .and_then(|pairs| {
+                pairs
+                    .iter()
+                    .map(|pair| {
+                        let mut pair = self.ctx.array_to_vec(pair)?.into_iter();
+                        let key = pair.next().unwrap_or_default();
+                        let value = pair.next().unwrap_or_default();
+
+                        Ok((self.ctx.value_to_atom(&key)?, key, value))
+                    })
+                    .collect::<Result<Vec<_>, _>>()
+            })
+            .map_err(|err| self.value_to_error(&err))?;
+
+        visitor
+            .visit_map(JsMapAccess {
+                map: self,
+                entries: entries.into_iter(),
+                next_value: None,
+            })
+            .map_err(|err| self.fix_path(err))
+    }
+
     fn derive_child_value<'r>(&'r self, key: &'a Atom<'rt>, value: &'r Value<'rt>) -> ValueDeserializer<'r, 'rt> {
         ValueDeserializer {
             parent: Some(self),
@@ -109,13 +243,10 @@ impl<'a, 'rt> Deserializer<'rt> for ValueDeserializer<'a, 'rt> {
             Value::String(_) => self.deserialize_to_string(visitor),
             Value::Module(_) => self.deserialize_map(visitor),
             Value::FunctionByteCode(_) => Err(self.new_error(super::ErrorRepr::SerializingFunctionCode)),
-            Value::Object(_) => {
-                if self.ctx.is_array(self.value) {
-                    self.deserialize_seq(visitor)
-                } else {
-                    self.deserialize_map(visitor)
-                }
-            }
+            Value::Object(_) => match self.ctx.classify(self.value) {
+                JsKind::Array => self.deserialize_array(visitor),
+                _ => self.deserialize_map(visitor),
+            },
             Value::Int32(v) | Value::ShortBigInt(v) => visitor.visit_i32(*v).map_err(|err| self.fix_path(err)),
             Value::Bool(v) => visitor.visit_bool(*v).map_err(|err| self.fix_path(err)),
             Value::Null | Value::Undefined | Value::Uninitialized => visitor.visit_unit().map_err(|err| self.fix_path(err)),
@@ -276,10 +407,30 @@ impl<'a, 'rt> Deserializer<'rt> for ValueDeserializer<'a, 'rt> {
         visitor.visit_unit().map_err(|err| self.fix_path(err))
     }
 
-    fn deserialize_newtype_struct<V>(self, _: &'static str, visitor: V) -> Result<V::Value, Self::Error>
+    fn deserialize_newtype_struct<V>(self, name: &'static str, visitor: V) -> Result<V::Value, Self::Error>
     where
         V: Visitor<'rt>,
     {
+        if name == DATE_MARKER && self.ctx.is_date(self.value) {
+            let epoch_ms = self.ctx.to_float64(self.value).map_err(|err| self.value_to_error(&err))?;
+
+            return visitor
+                .visit_newtype_struct(F64Deserializer::new(epoch_ms))
+                .map_err(|err| self.fix_path(err));
+        }
+
+        if name == JS_VALUE_TOKEN {
+            let value = self
+                .ctx
+                .runtime()
+                .new_global_value(self.value)
+                .map_err(|_| self.new_error(super::ErrorRepr::Custom("invalid runtime".to_string())))?;
+
+            capture_value(value);
+
+            return visitor.visit_unit().map_err(|err| self.fix_path(err));
+        }
+
         visitor.visit_newtype_struct(self.clone()).map_err(|err| self.fix_path(err))
     }
 
@@ -287,55 +438,8 @@ impl<'a, 'rt> Deserializer<'rt> for ValueDeserializer<'a, 'rt> {
     where
         V: Visitor<'rt>,
     {
-        if self.ctx.is_array(&self.value) {
-            struct ArrayAccess<'a, 'rt> {
-                array: &'a ValueDeserializer<'a, 'rt>,
-                index: u32,
-                length: u32,
-            }
-
-            impl<'a, 'rt> SeqAccess<'rt> for ArrayAccess<'a, 'rt> {
-                type Error = super::Error;
-
-                fn next_element_seed<T>(&mut self, seed: T) -> Result<Option<T::Value>, Self::Error>
-                where
-                    T: DeserializeSeed<'rt>,
-                {
-                    if self.index < self.length {
-                        let index = self
-                            .array
-                            .ctx
-                            .new_atom_uint32(self.index)
-                            .map_err(|err| self.array.value_to_error(&err))?;
-                        let elm = self
-                            .array
-                            .ctx
-                            .get_property(self.array.value, &index)
-                            .map_err(|err| self.array.value_to_error(&err))?;
-
-                        self.index += 1;
-
-                        let deserializer = self.array.derive_child_value(&index, &elm);
-                        seed.deserialize(deserializer.clone())
-                            .map(Some)
-                            .map_err(|err| deserializer.fix_path(err))
-                    } else {
-                        Ok(None)
-                    }
-                }
-
-                fn size_hint(&self) -> Option<usize> {
-                    Some((self.length - self.index) as usize)
-                }
-            }
-
-            visitor
-                .visit_seq(ArrayAccess {
-                    array: &self,
-                    index: 0,
-                    length: self.ctx.get_length(&self.value).map_err(|err| self.value_to_error(&err))? as _,
-                })
-                .map_err(|err| self.fix_path(err))
+        if matches!(self.ctx.classify(self.value), JsKind::Array) {
+            self.deserialize_array(visitor)
         } else {
             struct ObjectAsSeqAccess<'a, 'rt> {
                 object: &'a ValueDeserializer<'a, 'rt>,
@@ -392,24 +496,38 @@ impl<'a, 'rt> Deserializer<'rt> for ValueDeserializer<'a, 'rt> {
         }
     }
 
-    fn deserialize_tuple<V>(self, _: usize, visitor: V) -> Result<V::Value, Self::Error>
+    fn deserialize_tuple<V>(self, len: usize, visitor: V) -> Result<V::Value, Self::Error>
     where
         V: Visitor<'rt>,
     {
+        if matches!(self.ctx.classify(self.value), JsKind::Array) {
+            let length = self.ctx.get_length(self.value).map_err(|err| self.value_to_error(&err))?;
+            if length != len as i64 {
+                return Err(self.new_error(super::ErrorRepr::TupleLengthMismatch {
+                    expected: len,
+                    actual: length as _,
+                }));
+            }
+        }
+
         self.deserialize_seq(visitor)
     }
 
-    fn deserialize_tuple_struct<V>(self, _: &'static str, _: usize, visitor: V) -> Result<V::Value, Self::Error>
+    fn deserialize_tuple_struct<V>(self, _: &'static str, len: usize, visitor: V) -> Result<V::Value, Self::Error>
     where
         V: Visitor<'rt>,
     {
-        self.deserialize_seq(visitor)
+        self.deserialize_tuple(len, visitor)
     }
 
     fn deserialize_map<V>(self, visitor: V) -> Result<V::Value, Self::Error>
     where
         V: Visitor<'rt>,
     {
+        if self.ctx.is_map(self.value) {
+            return self.deserialize_js_map(visitor);
+        }
+
         struct ObjectAsMapAccess<'a, 'rt> {
             object: &'a ValueDeserializer<'a, 'rt>,
             atoms: Vec<OwnAtom<'rt>>,
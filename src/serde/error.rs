@@ -2,7 +2,7 @@ use crate::{Atom, Context, Value};
 
 pub fn error_to_string<'rt>(ctx: &Context, err: &Value) -> String {
     ctx.to_string(err)
-        .and_then(|s| ctx.get_string(&s).map(|s| s.to_string()))
+        .and_then(|s| ctx.get_string_lossy(&s))
         .unwrap_or_else(|_| "internal error".to_string())
 }
 
@@ -14,7 +14,7 @@ pub fn collect_path<'a, T, K: FnMut(T) -> Option<&'a Atom<'a>>, C: IntoIterator<
     let mut path = Vec::new();
     for holder in holders {
         if let Some(key) = k(holder) {
-            if let Ok(s) = ctx.atom_to_string(key).and_then(|v| Ok(ctx.get_string(&v)?.to_string())) {
+            if let Ok(s) = ctx.atom_to_string(key).and_then(|v| ctx.get_string_lossy(&v)) {
                 path.push(s.to_string());
             } else {
                 path.push("<unknown>".to_string());
@@ -2,12 +2,14 @@ mod de;
 mod error;
 mod pool;
 mod ser;
+mod value;
 
 use std::fmt::{Debug, Display, Formatter};
 
 pub use self::{
     de::{from_value, from_values},
-    ser::{to_value, to_values},
+    ser::{to_value, to_value_ordered, to_values},
+    value::{DATE_MARKER, JsDate, JsValue},
 };
 
 #[derive(Debug)]
@@ -19,6 +21,7 @@ pub enum ErrorRepr {
     ExceptingArrayBuffer,
     ExpectingObject,
     ExpectingArray,
+    TupleLengthMismatch { expected: usize, actual: usize },
 }
 
 pub struct Error {
@@ -76,6 +79,9 @@ impl Display for Error {
             ErrorRepr::ExceptingArrayBuffer => write!(f, "parse {}: excepting array buffer", path),
             ErrorRepr::ExpectingObject => write!(f, "parse {}: expecting object", path),
             ErrorRepr::ExpectingArray => write!(f, "parse {}: expecting array", path),
+            ErrorRepr::TupleLengthMismatch { expected, actual } => {
+                write!(f, "parse {}: expecting array of length {}, got {}", path, expected, actual)
+            }
         }
     }
 }
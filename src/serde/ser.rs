@@ -6,7 +6,7 @@ use serde::{
     },
 };
 
-use super::{error::error_to_string, pool::AtomPool};
+use super::{DATE_MARKER, error::error_to_string, pool::AtomPool};
 use crate::{Atom, Context, Value, serde::error::collect_path};
 
 #[derive(Clone)]
@@ -15,15 +15,17 @@ pub struct ValueSerializer<'a, 'rt> {
     ctx: &'a Context<'rt>,
     key: Option<&'a Atom<'rt>>,
     atom_pool: &'a AtomPool<'rt>,
+    ordered_maps: bool,
 }
 
 impl<'a, 'rt> ValueSerializer<'a, 'rt> {
-    fn new(ctx: &'a Context<'rt>, atom_pool: &'a AtomPool<'rt>) -> Self {
+    fn new(ctx: &'a Context<'rt>, atom_pool: &'a AtomPool<'rt>, ordered_maps: bool) -> Self {
         Self {
             parent: None,
             ctx,
             key: None,
             atom_pool,
+            ordered_maps,
         }
     }
 }
@@ -53,8 +55,29 @@ impl<'a, 'rt> ValueSerializer<'a, 'rt> {
             ctx: self.ctx,
             key: Some(key),
             atom_pool: self.atom_pool,
+            ordered_maps: self.ordered_maps,
         }
     }
+
+    fn serialize_object(self, ordered: bool) -> Result<ObjectValueSerializer<'a, 'rt>, super::Error> {
+        let object = if ordered {
+            // Map keeps insertion order for every key, objects always list integer-like keys first
+            self.ctx
+                .get_property_str(self.ctx.global(), "Map")
+                .and_then(|ctor| self.ctx.call_constructor(&ctor, None, &[]))
+        } else {
+            self.ctx.new_object(None)
+        };
+
+        Ok(ObjectValueSerializer {
+            ctx: self.ctx,
+            atom_pool: self.atom_pool,
+            object: object.map_err(|err| self.value_to_error(&err))?,
+            ordered,
+            next_key: None,
+            parent: self,
+        })
+    }
 }
 
 impl<'a, 'rt> Serializer for ValueSerializer<'a, 'rt> {
@@ -131,7 +154,7 @@ impl<'a, 'rt> Serializer for ValueSerializer<'a, 'rt> {
     fn serialize_bytes(self, v: &[u8]) -> Result<Self::Ok, Self::Error> {
         self.ctx
             .new_array_buffer(v.to_vec(), false)
-            .map_err(|err| self.value_to_error(&err))
+            .map_err(|err| self.value_to_error(&err.into()))
     }
 
     fn serialize_none(self) -> Result<Self::Ok, Self::Error> {
@@ -157,10 +180,18 @@ impl<'a, 'rt> Serializer for ValueSerializer<'a, 'rt> {
         self.serialize_str(variant)
     }
 
-    fn serialize_newtype_struct<T>(self, _: &'static str, value: &T) -> Result<Self::Ok, Self::Error>
+    fn serialize_newtype_struct<T>(self, name: &'static str, value: &T) -> Result<Self::Ok, Self::Error>
     where
         T: ?Sized + Serialize,
     {
+        if name == DATE_MARKER {
+            let epoch_ms = value.serialize(self.clone())?;
+            let epoch_ms = f64::try_from(&epoch_ms)
+                .map_err(|_| self.new_error(super::ErrorRepr::Custom("expecting epoch millis for date".to_string())))?;
+
+            return self.ctx.new_date(epoch_ms).map_err(|err| self.value_to_error(&err));
+        }
+
         value.serialize(self)
     }
 
@@ -199,17 +230,13 @@ impl<'a, 'rt> Serializer for ValueSerializer<'a, 'rt> {
     }
 
     fn serialize_map(self, _: Option<usize>) -> Result<Self::SerializeMap, Self::Error> {
-        Ok(ObjectValueSerializer {
-            ctx: self.ctx,
-            atom_pool: self.atom_pool,
-            object: self.ctx.new_object(None).map_err(|err| self.value_to_error(&err))?,
-            next_key: None,
-            parent: self,
-        })
+        let ordered = self.ordered_maps;
+
+        self.serialize_object(ordered)
     }
 
-    fn serialize_struct(self, _: &'static str, len: usize) -> Result<Self::SerializeStruct, Self::Error> {
-        self.serialize_map(Some(len))
+    fn serialize_struct(self, _: &'static str, _: usize) -> Result<Self::SerializeStruct, Self::Error> {
+        self.serialize_object(false)
     }
 
     fn serialize_struct_variant(
@@ -217,9 +244,9 @@ impl<'a, 'rt> Serializer for ValueSerializer<'a, 'rt> {
         _: &'static str,
         _: u32,
         _: &'static str,
-        len: usize,
+        _: usize,
     ) -> Result<Self::SerializeStructVariant, Self::Error> {
-        self.serialize_map(Some(len))
+        self.serialize_object(false)
     }
 }
 
@@ -312,7 +339,8 @@ pub struct ObjectValueSerializer<'a, 'rt> {
     ctx: &'a Context<'rt>,
     atom_pool: &'a AtomPool<'rt>,
     object: Value<'rt>,
-    next_key: Option<Atom<'rt>>,
+    ordered: bool,
+    next_key: Option<(Atom<'rt>, Value<'rt>)>,
 }
 
 impl<'a, 'rt> SerializeMap for ObjectValueSerializer<'a, 'rt> {
@@ -324,8 +352,9 @@ impl<'a, 'rt> SerializeMap for ObjectValueSerializer<'a, 'rt> {
         T: ?Sized + Serialize,
     {
         let key = key.serialize(self.parent.clone())?;
+        let atom = self.ctx.value_to_atom(&key).map_err(|err| self.parent.value_to_error(&err))?;
 
-        self.next_key = Some(self.ctx.value_to_atom(&key).map_err(|err| self.parent.value_to_error(&err))?);
+        self.next_key = Some((atom, key));
 
         Ok(())
     }
@@ -334,14 +363,25 @@ impl<'a, 'rt> SerializeMap for ObjectValueSerializer<'a, 'rt> {
     where
         T: ?Sized + Serialize,
     {
-        let key = self.next_key.take().expect("key is None");
+        let (atom, key) = self.next_key.take().expect("key is None");
 
-        let ser = self.parent.derive_child_value(&key);
+        let ser = self.parent.derive_child_value(&atom);
         let value = value.serialize(ser.clone())?;
 
-        self.ctx
-            .set_property(&self.object, &key, value)
-            .map_err(|err| ser.value_to_error(&err))?;
+        if self.ordered {
+            let set = self
+                .atom_pool
+                .get_or_create(self.ctx, "set")
+                .map_err(|err| ser.value_to_error(&err))?;
+
+            self.ctx
+                .invoke(&self.object, &set, &[key, value])
+                .map_err(|err| ser.value_to_error(&err))?;
+        } else {
+            self.ctx
+                .set_property(&self.object, &atom, value)
+                .map_err(|err| ser.value_to_error(&err))?;
+        }
 
         Ok(())
     }
@@ -359,12 +399,12 @@ impl<'a, 'rt> SerializeStruct for ObjectValueSerializer<'a, 'rt> {
     where
         T: ?Sized + Serialize,
     {
-        let key = self
+        let atom = self
             .atom_pool
             .get_or_create(self.ctx, key)
             .map_err(|err| self.parent.value_to_error(&err))?;
 
-        self.next_key = Some(key);
+        self.next_key = Some((atom, Value::Undefined));
 
         SerializeMap::serialize_value(self, value)
     }
@@ -392,7 +432,14 @@ impl<'a, 'rt> SerializeStructVariant for ObjectValueSerializer<'a, 'rt> {
 
 pub fn to_value<'rt, S: Serialize>(ctx: &Context<'rt>, value: S) -> Result<Value<'rt>, super::Error> {
     let pool = AtomPool::new();
-    let serializer = ValueSerializer::new(ctx, &pool);
+    let serializer = ValueSerializer::new(ctx, &pool, false);
+    value.serialize(serializer)
+}
+
+// maps become JS Maps, so integer-like keys keep their insertion order
+pub fn to_value_ordered<'rt, S: Serialize>(ctx: &Context<'rt>, value: S) -> Result<Value<'rt>, super::Error> {
+    let pool = AtomPool::new();
+    let serializer = ValueSerializer::new(ctx, &pool, true);
     value.serialize(serializer)
 }
 
@@ -401,7 +448,7 @@ pub fn to_values<'rt, S: Serialize>(ctx: &Context<'rt>, values: &[S]) -> Result<
     let ret = values
         .iter()
         .map(|value| {
-            let serializer = ValueSerializer::new(ctx, &pool);
+            let serializer = ValueSerializer::new(ctx, &pool, false);
             value.serialize(serializer)
         })
         .collect::<Result<Vec<_>, _>>()?;
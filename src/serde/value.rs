@@ -0,0 +1,89 @@
+use std::{cell::RefCell, fmt::Formatter};
+
+use serde::{
+    Deserialize, Deserializer, Serialize, Serializer,
+    de::{Error, Visitor},
+};
+
+use crate::GlobalValue;
+
+pub(crate) const JS_VALUE_TOKEN: &str = "$libquickjs::JsValue";
+
+pub const DATE_MARKER: &str = "$__js_date";
+
+thread_local! {
+    static CAPTURED: RefCell<Option<GlobalValue>> = const { RefCell::new(None) };
+}
+
+pub(crate) fn capture_value(value: GlobalValue) {
+    CAPTURED.with(|captured| *captured.borrow_mut() = Some(value));
+}
+
+// keeps the deserialized subtree as a js value instead of converting it
+#[derive(Clone)]
+pub struct JsValue(pub GlobalValue);
+
+impl<'de> Deserialize<'de> for JsValue {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        struct JsValueVisitor;
+
+        impl<'de> Visitor<'de> for JsValueVisitor {
+            type Value = JsValue;
+
+            fn expecting(&self, formatter: &mut Formatter) -> std::fmt::Result {
+                formatter.write_str("a quickjs value")
+            }
+
+            fn visit_unit<E: Error>(self) -> Result<Self::Value, E> {
+                CAPTURED
+                    .with(|captured| captured.borrow_mut().take())
+                    .map(JsValue)
+                    .ok_or_else(|| E::custom("JsValue can only be deserialized from quickjs values"))
+            }
+        }
+
+        deserializer.deserialize_newtype_struct(JS_VALUE_TOKEN, JsValueVisitor)
+    }
+}
+
+// epoch millis, (de)serialized as js Date through DATE_MARKER
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct JsDate(pub f64);
+
+impl Serialize for JsDate {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.serialize_newtype_struct(DATE_MARKER, &self.0)
+    }
+}
+
+impl<'de> Deserialize<'de> for JsDate {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        struct JsDateVisitor;
+
+        impl<'de> Visitor<'de> for JsDateVisitor {
+            type Value = JsDate;
+
+            fn expecting(&self, formatter: &mut Formatter) -> std::fmt::Result {
+                formatter.write_str("a date")
+            }
+
+            fn visit_newtype_struct<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+            where
+                D: Deserializer<'de>,
+            {
+                f64::deserialize(deserializer).map(JsDate)
+            }
+        }
+
+        deserializer.deserialize_newtype_struct(DATE_MARKER, JsDateVisitor)
+    }
+}
@@ -19,6 +19,7 @@ fn test_global_values() {
             global_contexts,
             global_refs,
             global_atoms,
+            ..
         } => {
             global_contexts.borrow_mut().cleanup();
             global_refs.borrow_mut().cleanup();
@@ -66,6 +67,7 @@ fn test_global_auto_cleanup() {
             global_contexts,
             global_refs,
             global_atoms,
+            ..
         } => {
             assert_eq!(class_ids.borrow().len(), 0);
             assert_eq!(global_contexts.borrow().len(), 1);
@@ -68,6 +68,10 @@ impl<V> GlobalHolder<V> {
         self.records.len()
     }
 
+    pub fn live_count(&self) -> usize {
+        self.records.iter().filter(|r| r.refs.strong_count() > 0).count()
+    }
+
     pub fn cleanup(&mut self) {
         if self.shared.dirty.swap(false, Ordering::Relaxed) {
             self.records.retain_mut(|v| {
@@ -0,0 +1,66 @@
+// quickjs-ng has no extended json parser, strip comments and trailing commas before JS_ParseJSON
+pub fn strip_json_extensions(json: &str) -> String {
+    let mut stripped = String::with_capacity(json.len());
+
+    let mut chars = json.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '"' => {
+                stripped.push(c);
+
+                while let Some(c) = chars.next() {
+                    stripped.push(c);
+
+                    match c {
+                        '\\' => stripped.extend(chars.next()),
+                        '"' => break,
+                        _ => {}
+                    }
+                }
+            }
+            '/' if chars.peek() == Some(&'/') => while chars.next_if(|&c| c != '\n').is_some() {},
+            '/' if chars.peek() == Some(&'*') => {
+                chars.next();
+
+                // keep line breaks so that error positions still match the input
+                let mut last = '\0';
+                for c in chars.by_ref() {
+                    if last == '*' && c == '/' {
+                        break;
+                    }
+                    if c == '\n' {
+                        stripped.push(c);
+                    }
+                    last = c;
+                }
+            }
+            _ => stripped.push(c),
+        }
+    }
+
+    let mut output = String::with_capacity(stripped.len());
+
+    let mut in_string = false;
+    let mut escaped = false;
+    for (pos, c) in stripped.char_indices() {
+        if in_string {
+            match c {
+                _ if escaped => escaped = false,
+                '\\' => escaped = true,
+                '"' => in_string = false,
+                _ => {}
+            }
+        } else if c == '"' {
+            in_string = true;
+        } else if c == ',' {
+            let next = stripped[pos + 1..].trim_start().chars().next();
+            if matches!(next, Some(']' | '}')) {
+                continue;
+            }
+        }
+
+        output.push(c);
+    }
+
+    output
+}
@@ -1,4 +1,6 @@
 pub mod cstr;
 pub mod global;
+pub mod json;
+pub mod module;
 pub mod ptr;
 pub mod vec;
@@ -0,0 +1,42 @@
+// port of js_default_module_normalize_name
+pub fn normalize_module_name(base: &str, name: &str) -> String {
+    if !name.starts_with('.') {
+        return name.to_string();
+    }
+
+    let mut filename = match base.rfind('/') {
+        Some(pos) => base[..pos].to_string(),
+        None => String::new(),
+    };
+
+    let mut r = name;
+    loop {
+        if let Some(rest) = r.strip_prefix("./") {
+            r = rest;
+        } else if let Some(rest) = r.strip_prefix("../") {
+            if filename.is_empty() {
+                break;
+            }
+
+            let last = match filename.rfind('/') {
+                Some(pos) => pos + 1,
+                None => 0,
+            };
+            if &filename[last..] == "." || &filename[last..] == ".." {
+                break;
+            }
+
+            filename.truncate(last.saturating_sub(1));
+            r = rest;
+        } else {
+            break;
+        }
+    }
+
+    if !filename.is_empty() {
+        filename.push('/');
+    }
+    filename.push_str(r);
+
+    filename
+}
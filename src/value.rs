@@ -1,13 +1,14 @@
 use std::{
     ffi::c_void,
-    fmt::{Debug, Formatter},
+    fmt::{Debug, Display, Formatter},
+    hash::{Hash, Hasher},
 };
 
 use rquickjs_sys::{
-    JS_EXCEPTION, JS_FreeCString, JS_MKPTR, JS_MKVAL, JS_NULL, JS_NewFloat64, JS_TAG_BIG_INT, JS_TAG_BOOL, JS_TAG_CATCH_OFFSET,
-    JS_TAG_EXCEPTION, JS_TAG_FLOAT64, JS_TAG_FUNCTION_BYTECODE, JS_TAG_INT, JS_TAG_MODULE, JS_TAG_NULL, JS_TAG_OBJECT,
-    JS_TAG_SHORT_BIG_INT, JS_TAG_STRING, JS_TAG_SYMBOL, JS_TAG_UNDEFINED, JS_TAG_UNINITIALIZED, JS_ToCStringLen, JS_UNDEFINED,
-    JS_UNINITIALIZED, JS_VALUE_IS_NAN, JSValue, JSValueUnion,
+    JS_EXCEPTION, JS_FreeCString, JS_GetException, JS_MKPTR, JS_MKVAL, JS_NULL, JS_NewFloat64, JS_TAG_BIG_INT, JS_TAG_BOOL,
+    JS_TAG_CATCH_OFFSET, JS_TAG_EXCEPTION, JS_TAG_FLOAT64, JS_TAG_FUNCTION_BYTECODE, JS_TAG_INT, JS_TAG_MODULE, JS_TAG_NULL,
+    JS_TAG_OBJECT, JS_TAG_SHORT_BIG_INT, JS_TAG_STRING, JS_TAG_SYMBOL, JS_TAG_UNDEFINED, JS_TAG_UNINITIALIZED, JS_ToCStringLen,
+    JS_UNDEFINED, JS_UNINITIALIZED, JS_VALUE_IS_NAN, JSValue, JSValueUnion,
 };
 
 use crate::Runtime;
@@ -32,6 +33,15 @@ impl<'rt, const TAG: i32> PartialEq for RefValue<'rt, TAG> {
     }
 }
 
+impl<'rt, const TAG: i32> Eq for RefValue<'rt, TAG> {}
+
+impl<'rt, const TAG: i32> Hash for RefValue<'rt, TAG> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.rt.ptr.hash(state);
+        self.ptr.hash(state);
+    }
+}
+
 impl<'rt, const TAG: i32> RefValue<'rt, TAG> {
     pub fn get_runtime(&self) -> &'rt Runtime {
         self.rt
@@ -40,22 +50,31 @@ impl<'rt, const TAG: i32> RefValue<'rt, TAG> {
 
 impl<'rt, const TAG: i32> Debug for RefValue<'rt, TAG> {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
-        unsafe {
-            let ctx = self.rt.new_context();
+        if self.rt.is_debug_redacted() {
+            return f.write_fmt(format_args!("Ref(tag: {}, ptr: {:?})", TAG, self.ptr));
+        }
+
+        let Some(ctx) = self.rt.debug_context() else {
+            return f.write_fmt(format_args!("Ref(tag: {}, ptr: {:?})", TAG, self.ptr));
+        };
 
+        unsafe {
             let mut length = 0;
-            let data = JS_ToCStringLen(ctx.as_raw().as_ptr(), &mut length, self.as_raw());
+            let data = JS_ToCStringLen(ctx.as_ptr(), &mut length, self.as_raw());
             if data.is_null() {
+                // printing must not leave an exception behind for the caller
+                rquickjs_sys::JS_FreeValueRT(self.rt.as_raw().as_ptr(), JS_GetException(ctx.as_ptr()));
+
                 f.write_fmt(format_args!("Ref(tag: {}, ptr: {:?})", TAG, self.ptr))
             } else {
                 f.write_fmt(format_args!(
                     "Ref(tag: {}, ptr: {:?}, value: {})",
                     TAG,
                     self.ptr,
-                    std::str::from_utf8_unchecked(std::slice::from_raw_parts(data as _, length))
+                    std::string::String::from_utf8_lossy(std::slice::from_raw_parts(data as _, length))
                 ))?;
 
-                JS_FreeCString(ctx.as_raw().as_ptr(), data);
+                JS_FreeCString(ctx.as_ptr(), data);
 
                 Ok(())
             }
@@ -128,6 +147,14 @@ impl<'rt> Value<'rt> {
             _ => false,
         }
     }
+
+    pub fn is_module(&self) -> bool {
+        matches!(self, Self::Module(_))
+    }
+
+    pub fn is_function_bytecode(&self) -> bool {
+        matches!(self, Self::FunctionByteCode(_))
+    }
 }
 
 impl<'rt> Default for Value<'rt> {
@@ -197,6 +224,51 @@ impl From<f64> for Value<'_> {
     }
 }
 
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct InvalidValueType;
+
+impl Display for InvalidValueType {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        Debug::fmt(self, f)
+    }
+}
+
+impl std::error::Error for InvalidValueType {}
+
+impl<'rt> TryFrom<&Value<'rt>> for i32 {
+    type Error = InvalidValueType;
+
+    fn try_from(value: &Value<'rt>) -> Result<Self, Self::Error> {
+        match value {
+            Value::Int32(v) => Ok(*v),
+            _ => Err(InvalidValueType),
+        }
+    }
+}
+
+impl<'rt> TryFrom<&Value<'rt>> for f64 {
+    type Error = InvalidValueType;
+
+    fn try_from(value: &Value<'rt>) -> Result<Self, Self::Error> {
+        match value {
+            Value::Int32(v) => Ok(*v as f64),
+            Value::Float64(v) => Ok(*v),
+            _ => Err(InvalidValueType),
+        }
+    }
+}
+
+impl<'rt> TryFrom<&Value<'rt>> for bool {
+    type Error = InvalidValueType;
+
+    fn try_from(value: &Value<'rt>) -> Result<Self, Self::Error> {
+        match value {
+            Value::Bool(v) => Ok(*v),
+            _ => Err(InvalidValueType),
+        }
+    }
+}
+
 impl<'rt> Value<'rt> {
     pub unsafe fn from_raw(rt: &'rt Runtime, value: JSValue) -> Result<Self, Exception> {
         unsafe {
@@ -0,0 +1,184 @@
+use std::num::NonZeroUsize;
+
+use libquickjs::{BufferAllocError, EvalFlags, Runtime, TypedArrayType, Value};
+
+#[test]
+fn test_typed_array_element_size() {
+    let rt = Runtime::new();
+    let ctx = rt.new_context();
+
+    for (code, kind) in [
+        ("new Uint8Array(4)", TypedArrayType::UINT8),
+        ("new Int16Array(4)", TypedArrayType::INT16),
+        ("new Float32Array(4)", TypedArrayType::FLOAT32),
+        ("new BigInt64Array(4)", TypedArrayType::BIG_INT64),
+        ("new Float64Array(4)", TypedArrayType::FLOAT64),
+    ] {
+        let array = ctx.eval_global(None, code, "test.js", EvalFlags::STRICT).unwrap();
+
+        let array_kind = ctx.get_typed_array_type(&array).unwrap();
+        assert_eq!(array_kind, kind);
+
+        let (_, _, length, bytes_per_element) = ctx.get_typed_array_buffer(&array).unwrap();
+        assert_eq!(array_kind.bytes_per_element(), bytes_per_element);
+        assert_eq!(length / bytes_per_element, 4);
+        assert_eq!(array_kind.is_bigint(), kind == TypedArrayType::BIG_INT64);
+    }
+}
+
+#[test]
+fn test_array_buffer_to_vec() {
+    let rt = Runtime::new();
+    let ctx = rt.new_context();
+
+    let buffer = ctx
+        .eval_global(None, "new Uint8Array([1, 2, 3, 4]).buffer", "test.js", EvalFlags::STRICT)
+        .unwrap();
+    assert_eq!(ctx.array_buffer_to_vec(&buffer).unwrap(), vec![1, 2, 3, 4]);
+
+    ctx.detach_array_buffer(&buffer).unwrap();
+    assert!(ctx.array_buffer_to_vec(&buffer).is_err());
+
+    let not_buffer = ctx.new_object(None).unwrap();
+    assert!(ctx.array_buffer_to_vec(&not_buffer).is_err());
+}
+
+#[test]
+fn test_new_array_buffer_borrowed() {
+    let rt = Runtime::new();
+    let ctx = rt.new_context();
+
+    let data: &'static mut [u8] = Box::leak(vec![1u8, 2, 3, 4].into_boxed_slice());
+    let ptr = data.as_ptr();
+
+    let buffer = ctx.new_array_buffer_borrowed(data).unwrap();
+    assert!(ctx.is_array_buffer(&buffer));
+
+    unsafe {
+        let view = ctx.get_array_buffer(&buffer).unwrap();
+        assert_eq!(view.as_ptr(), ptr);
+        assert_eq!(view, &[1, 2, 3, 4]);
+    }
+
+    let global_obj = ctx.get_global_object();
+    ctx.set_property_str(&global_obj, "buffer", buffer).unwrap();
+    let sum = ctx
+        .eval_global(
+            None,
+            "new Uint8Array(buffer).reduce((a, b) => a + b)",
+            "test.js",
+            EvalFlags::STRICT,
+        )
+        .unwrap();
+    assert_eq!(sum, Value::Int32(10));
+}
+
+#[test]
+fn test_is_typed_array() {
+    let rt = Runtime::new();
+    let ctx = rt.new_context();
+
+    let array = ctx
+        .eval_global(None, "new Float64Array(2)", "test.js", EvalFlags::STRICT)
+        .unwrap();
+    assert!(ctx.is_typed_array(&array));
+
+    let buffer = ctx.new_array_buffer_copy(&[1, 2]).unwrap();
+    assert!(!ctx.is_typed_array(&buffer));
+    assert!(!ctx.is_typed_array(&ctx.new_array().unwrap()));
+    assert!(!ctx.is_typed_array(&Value::Int32(1)));
+}
+
+#[test]
+fn test_typed_array_get_f64() {
+    let rt = Runtime::new();
+    let ctx = rt.new_context();
+
+    for code in [
+        "new Uint8Array([1, 2, 255])",
+        "new Int16Array([1, 2, 255])",
+        "new Float32Array([1, 2, 255])",
+    ] {
+        let array = ctx.eval_global(None, code, "test.js", EvalFlags::STRICT).unwrap();
+
+        assert_eq!(ctx.typed_array_get_f64(&array, 0).unwrap(), 1.0);
+        assert_eq!(ctx.typed_array_get_f64(&array, 2).unwrap(), 255.0);
+        assert!(ctx.typed_array_get_f64(&array, 3).unwrap().is_nan());
+    }
+
+    let floats = ctx
+        .eval_global(None, "new Float64Array([0.5, -1.25])", "test.js", EvalFlags::STRICT)
+        .unwrap();
+    assert_eq!(ctx.typed_array_get_f64(&floats, 1).unwrap(), -1.25);
+
+    let plain = ctx.eval_global(None, "[1, 2, 3]", "test.js", EvalFlags::STRICT).unwrap();
+    assert!(ctx.typed_array_get_f64(&plain, 0).is_err());
+}
+
+#[test]
+fn test_array_buffer_as_ref() {
+    let rt = Runtime::new();
+    let ctx = rt.new_context();
+
+    let buffer = ctx
+        .eval_global(
+            None,
+            "globalThis.buffer = new Uint8Array([1, 2, 3, 250]).buffer",
+            "test.js",
+            EvalFlags::STRICT,
+        )
+        .unwrap();
+
+    let sum = ctx
+        .array_buffer_as_ref(&buffer, |data| data.iter().map(|b| *b as u32).sum::<u32>())
+        .unwrap();
+    assert_eq!(sum, 256);
+
+    let not_buffer = ctx.new_object(None).unwrap();
+    assert!(ctx.array_buffer_as_ref(&not_buffer, |data| data.len()).is_err());
+
+    // detaching from inside the closure leaves the slice intact
+    let last = ctx
+        .array_buffer_as_ref(&buffer, |data| {
+            ctx.eval_global(None, "globalThis.moved = buffer.transfer()", "test.js", EvalFlags::STRICT)
+                .unwrap();
+            data[3]
+        })
+        .unwrap();
+    assert_eq!(last, 250);
+}
+
+#[test]
+fn test_new_uint8_array_from_vec() {
+    let rt = Runtime::new();
+    let ctx = rt.new_context();
+
+    let data = vec![1u8, 2, 3, 4];
+    let ptr = data.as_ptr();
+
+    let array = ctx.new_uint8_array_from_vec(data).unwrap();
+    assert_eq!(ctx.get_typed_array_type(&array).unwrap(), TypedArrayType::UINT8);
+
+    unsafe {
+        let view = ctx.get_uint8_array(&array).unwrap();
+        assert_eq!(view.as_ptr(), ptr);
+        assert_eq!(view, &[1, 2, 3, 4]);
+    }
+}
+
+#[test]
+fn test_buffer_alloc_error() {
+    let rt = Runtime::new();
+    let ctx = rt.new_context();
+
+    rt.set_memory_limit(NonZeroUsize::new(4 * 1024 * 1024));
+
+    let data = vec![0u8; 8 * 1024 * 1024];
+    let err = ctx.new_array_buffer_copy(&data).unwrap_err();
+    assert!(matches!(err, BufferAllocError::OutOfMemory(_)));
+
+    rt.set_memory_limit(None);
+
+    let buffer = ctx.new_array_buffer_copy(&data).unwrap();
+    assert!(ctx.is_array_buffer(&buffer));
+}
@@ -0,0 +1,110 @@
+use libquickjs::{EvalFlags, NativeFunctionExt, Runtime, Value};
+
+#[test]
+fn test_throw_error() {
+    let rt = Runtime::new();
+    let ctx = rt.new_context();
+
+    ctx.define_native_function(&ctx.get_global_object(), "fail", |ctx, _, _, _, _| {
+        Err(ctx.throw_error("native failure"))
+    })
+    .unwrap();
+
+    let ret = ctx
+        .eval_global(
+            None,
+            "try { fail() } catch (e) { `${e instanceof Error}:${e.message}` }",
+            "test.js",
+            EvalFlags::STRICT,
+        )
+        .unwrap();
+    assert_eq!(&*ctx.get_string(&ret).unwrap(), "true:native failure");
+
+    // backed by JS_ThrowPlainError, nothing is left pending and '%' is kept verbatim
+    let err = ctx.throw_error("100% plain");
+    assert_eq!(ctx.exception_message(&err).as_deref(), Some("100% plain"));
+    unsafe { assert!(!rquickjs_sys::JS_HasException(ctx.as_raw().as_ptr())) };
+}
+
+#[cfg(all(feature = "backtrace", debug_assertions))]
+#[test]
+fn test_error_rust_backtrace() {
+    let rt = Runtime::new();
+    let ctx = rt.new_context();
+
+    let error = ctx.new_error_with("boom").unwrap();
+    let backtrace = ctx.get_property_str(&error, "rustBacktrace").unwrap();
+    assert!(ctx.get_string(&backtrace).unwrap().contains("test_error_rust_backtrace"));
+
+    let error = ctx.throw_error("boom");
+    let backtrace = ctx.get_property_str(&error, "rustBacktrace").unwrap();
+    assert!(ctx.get_string(&backtrace).unwrap().contains("test_error_rust_backtrace"));
+}
+
+#[test]
+fn test_clear_exception() {
+    let rt = Runtime::new();
+    let ctx = rt.new_context();
+
+    unsafe {
+        rquickjs_sys::JS_ThrowTypeError(ctx.as_raw().as_ptr(), c"pending".as_ptr());
+        assert!(rquickjs_sys::JS_HasException(ctx.as_raw().as_ptr()));
+    }
+
+    ctx.clear_exception();
+
+    unsafe { assert!(!rquickjs_sys::JS_HasException(ctx.as_raw().as_ptr())) };
+
+    // clearing without a pending exception is a no-op
+    ctx.clear_exception();
+}
+
+#[test]
+fn test_exception_message_and_stack() {
+    let rt = Runtime::new();
+    let ctx = rt.new_context();
+
+    let err = ctx
+        .eval_global(None, "throw new Error('boom')", "test.js", EvalFlags::STRICT)
+        .unwrap_err();
+    assert_eq!(ctx.exception_message(&err).as_deref(), Some("boom"));
+    assert!(!ctx.exception_stack(&err).unwrap().is_empty());
+
+    // non-error values carry neither
+    let err = ctx.eval_global(None, "throw 42", "test.js", EvalFlags::STRICT).unwrap_err();
+    assert_eq!(ctx.exception_message(&err), None);
+    assert_eq!(ctx.exception_stack(&err), None);
+}
+
+#[test]
+fn test_throw_error_subtypes() {
+    let rt = Runtime::new();
+    let ctx = rt.new_context();
+
+    ctx.define_native_function(&ctx.get_global_object(), "expectNumber", |ctx, _, _, args, _| {
+        match args.first() {
+            Some(Value::Int32(_) | Value::Float64(_)) => Ok(Value::Undefined),
+            _ => Err(ctx.throw_type_error("expected a number, got 100%")),
+        }
+    })
+    .unwrap();
+
+    let ret = ctx
+        .eval_global(
+            None,
+            "try { expectNumber('1') } catch (e) { `${e instanceof TypeError}:${e.message}` }",
+            "test.js",
+            EvalFlags::STRICT,
+        )
+        .unwrap();
+    assert_eq!(&*ctx.get_string(&ret).unwrap(), "true:expected a number, got 100%");
+
+    for (err, ctor) in [
+        (ctx.throw_range_error("range"), "RangeError"),
+        (ctx.throw_syntax_error("syntax"), "SyntaxError"),
+        (ctx.throw_reference_error("reference"), "ReferenceError"),
+    ] {
+        let ctor = ctx.get_property_str(ctx.global(), ctor).unwrap();
+        assert!(ctx.is_instance_of(&err, &ctor).unwrap());
+    }
+}
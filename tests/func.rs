@@ -1,4 +1,4 @@
-use libquickjs::{EvalFlags, NativeFunction, PropertyDescriptorFlags, Runtime, Value};
+use libquickjs::{EvalFlags, NativeFunction, NativeFunctionExt, PropertyDescriptorFlags, Runtime, Value};
 
 #[test]
 fn test_call_native_func() {
@@ -33,3 +33,219 @@ fn test_call_native_func() {
     ctx.eval_global(None, "hello('world!!')", "test.js", EvalFlags::STRICT)
         .unwrap();
 }
+
+#[test]
+fn test_native_constructor_new_target() {
+    let rt = Runtime::new();
+    let ctx = rt.new_context();
+
+    let func = ctx
+        .new_object_class(
+            NativeFunction::new(|ctx, _, _, _, options| match options.new_target {
+                Some(new_target) => {
+                    assert!(options.constructor);
+
+                    ctx.new_object(Some(&ctx.get_property_str(new_target, "prototype")?))
+                }
+                None => Ok(Value::Undefined),
+            }),
+            None,
+        )
+        .unwrap();
+    ctx.set_constructor_bit(&func, true);
+    ctx.set_property_str(&func, "prototype", ctx.new_object(None).unwrap())
+        .unwrap();
+
+    assert_eq!(ctx.call(&func, &Value::Undefined, &[]).unwrap(), Value::Undefined);
+
+    let global_obj = ctx.get_global_object();
+    ctx.set_property_str(&global_obj, "Base", func).unwrap();
+
+    let ret = ctx
+        .eval_global(
+            None,
+            "class Derived extends Base {}; new Derived() instanceof Derived",
+            "test.js",
+            EvalFlags::STRICT,
+        )
+        .unwrap();
+    assert_eq!(ret, Value::Bool(true));
+}
+
+#[test]
+fn test_native_call_depth() {
+    let rt = Runtime::new();
+    let ctx = rt.new_context();
+
+    let func = ctx
+        .new_object_class(
+            NativeFunction::new(|ctx, _, _, args, _| {
+                let depth = ctx.call_depth();
+                if depth > 3 {
+                    return Err(ctx.new_string("too deep")?);
+                }
+
+                ctx.call(&args[0], &Value::Undefined, &[args[0].clone()])?;
+
+                Ok(Value::Int32(depth as i32))
+            }),
+            None,
+        )
+        .unwrap();
+
+    let global_obj = ctx.get_global_object();
+    ctx.set_property_str(&global_obj, "reenter", func).unwrap();
+
+    assert_eq!(ctx.call_depth(), 0);
+
+    let ret = ctx
+        .eval_global(
+            None,
+            "let depths = []; try { reenter(function f(g) { depths.push(reenter(f)); }); } catch (e) { depths.push(e); } depths.join(',')",
+            "test.js",
+            EvalFlags::STRICT,
+        )
+        .unwrap();
+    assert_eq!(&*ctx.get_string(&ret).unwrap(), "too deep");
+    assert_eq!(ctx.call_depth(), 0);
+}
+
+#[test]
+fn test_set_function_name() {
+    let rt = Runtime::new();
+    let ctx = rt.new_context();
+
+    let func = ctx
+        .new_object_class(NativeFunction::new(|_, _, _, _, _| Ok(Value::Undefined)), None)
+        .unwrap();
+    ctx.set_function_name(&func, "hostFunction").unwrap();
+
+    let global_obj = ctx.get_global_object();
+    ctx.set_property_str(&global_obj, "f", func).unwrap();
+
+    let ret = ctx
+        .eval_global(
+            None,
+            r#"const desc = Object.getOwnPropertyDescriptor(f, "name"); `${f.name}:${desc.writable}:${desc.enumerable}:${desc.configurable}`"#,
+            "test.js",
+            EvalFlags::STRICT,
+        )
+        .unwrap();
+    assert_eq!(&*ctx.get_string(&ret).unwrap(), "hostFunction:false:false:true");
+}
+
+#[test]
+fn test_try_call_thrown_value() {
+    let rt = Runtime::new();
+    let ctx = rt.new_context();
+
+    let fail = ctx
+        .eval_global(
+            None,
+            "(kind) => { if (kind) throw new Error('boom'); throw 'plain'; }",
+            "test.js",
+            EvalFlags::STRICT,
+        )
+        .unwrap();
+
+    let thrown = ctx.try_call(&fail, &Value::Undefined, &[Value::Bool(true)]).unwrap_err();
+    assert!(thrown.is_error(&ctx));
+    assert_eq!(thrown.message(&ctx).as_deref(), Some("boom"));
+    assert!(thrown.stack(&ctx).is_some());
+
+    let thrown = ctx.try_call(&fail, &Value::Undefined, &[Value::Bool(false)]).unwrap_err();
+    assert!(!thrown.is_error(&ctx));
+    assert_eq!(thrown.message(&ctx), None);
+    assert_eq!(&*ctx.get_string(thrown.value()).unwrap(), "plain");
+}
+
+#[test]
+fn test_js_function() {
+    let rt = Runtime::new();
+    let ctx = rt.new_context();
+
+    let add = ctx
+        .eval_global(None, "(a, b) => a + b", "test.js", EvalFlags::STRICT)
+        .unwrap()
+        .as_function(&ctx)
+        .unwrap();
+    assert_eq!(
+        add.call(&ctx, &Value::Undefined, &[Value::Int32(1), Value::Int32(2)])
+            .unwrap(),
+        Value::Int32(3)
+    );
+
+    let answer = ctx
+        .eval_global(None, "() => 42", "test.js", EvalFlags::STRICT)
+        .unwrap()
+        .as_function(&ctx)
+        .unwrap();
+    assert_eq!(answer.call0(&ctx).unwrap(), Value::Int32(42));
+
+    let global = rt.new_global_value(answer.as_value()).unwrap();
+    let restored = global.to_local(&rt).unwrap().as_function(&ctx).unwrap();
+    assert_eq!(restored.call0(&ctx).unwrap(), Value::Int32(42));
+
+    assert!(Value::Int32(1).as_function(&ctx).is_none());
+}
+
+#[test]
+fn test_define_native_functions() {
+    let rt = Runtime::new();
+    let ctx = rt.new_context();
+
+    let global = ctx.get_global_object();
+    ctx.define_native_function(&global, "one", |_, _, _, _, _| Ok(Value::Int32(1)))
+        .unwrap();
+    ctx.define_native_function(&global, "two", |_, _, _, _, _| Ok(Value::Int32(2)))
+        .unwrap();
+
+    let ret = ctx
+        .eval_global(None, "one() + two() + one.name + two.name", "test.js", EvalFlags::STRICT)
+        .unwrap();
+    assert_eq!(&*ctx.get_string(&ret).unwrap(), "3onetwo");
+}
+
+#[test]
+fn test_function_source_info() {
+    let rt = Runtime::new();
+    let ctx = rt.new_context();
+
+    let func = ctx
+        .eval_global(
+            None,
+            "\n\nfunction target() {\n  return 1;\n}\ntarget",
+            "debug.js",
+            EvalFlags::STRICT,
+        )
+        .unwrap();
+
+    let info = ctx.function_source_info(&func).unwrap();
+    assert_eq!(info.filename, "debug.js");
+    assert_eq!(info.line, 3);
+    assert!(info.column > 0);
+
+    let native = ctx
+        .new_object_class(NativeFunction::new(|_, _, _, _, _| Ok(Value::Undefined)), None)
+        .unwrap();
+    assert_eq!(ctx.function_source_info(&native), None);
+    assert_eq!(ctx.function_source_info(&Value::Int32(1)), None);
+}
+
+#[test]
+fn test_call_simple() {
+    let rt = Runtime::new();
+    let ctx = rt.new_context();
+
+    let func = ctx
+        .eval_global(
+            None,
+            "(function (a, b) { return `${this}:${a + b}` })",
+            "test.js",
+            EvalFlags::STRICT,
+        )
+        .unwrap();
+
+    let ret = ctx.call_simple(&func, &[Value::Int32(1), Value::Int32(2)]).unwrap();
+    assert_eq!(&*ctx.get_string(&ret).unwrap(), "undefined:3");
+}
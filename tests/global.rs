@@ -1,4 +1,9 @@
-use libquickjs::Runtime;
+use std::sync::{
+    Arc,
+    atomic::{AtomicBool, Ordering},
+};
+
+use libquickjs::{EvalFlags, NativeFunctionExt, OutstandingHandles, Runtime, Value};
 
 #[test]
 fn test_global_objects() {
@@ -10,3 +15,101 @@ fn test_global_objects() {
     let global_obj = rt.new_global_value(&obj).unwrap();
     let _ = global_obj;
 }
+
+#[test]
+fn test_cached_global() {
+    let rt = Runtime::new();
+    let ctx = rt.new_context();
+
+    assert_eq!(ctx.global(), &ctx.get_global_object());
+    assert!(std::ptr::eq(ctx.global(), ctx.global()));
+
+    ctx.set_property_str(ctx.global(), "answer", Value::Int32(42)).unwrap();
+    ctx.define_native_function(ctx.global(), "lookup", |ctx, _, _, _, _| {
+        ctx.get_property_str(ctx.global(), "answer")
+    })
+    .unwrap();
+
+    let ret = ctx.eval_global(None, "lookup()", "test.js", EvalFlags::STRICT).unwrap();
+    assert_eq!(ret, Value::Int32(42));
+}
+
+#[test]
+fn test_try_shutdown() {
+    let rt = Runtime::new();
+    {
+        let ctx = rt.new_context();
+        let obj = ctx.new_object(None).unwrap();
+        let _ = rt.new_global_value(&obj).unwrap();
+    }
+    assert_eq!(rt.try_shutdown(), Ok(()));
+
+    let rt = Runtime::new();
+    let (value, context, atom) = {
+        let ctx = rt.new_context();
+        let obj = ctx.new_object(None).unwrap();
+        let atom = ctx.new_atom("key").unwrap();
+
+        (
+            rt.new_global_value(&obj).unwrap(),
+            rt.new_global_context(&ctx).unwrap(),
+            ctx.new_global_atom(&atom),
+        )
+    };
+    assert_eq!(
+        rt.try_shutdown(),
+        Err(OutstandingHandles {
+            values: 1,
+            contexts: 1,
+            atoms: 1,
+        })
+    );
+
+    drop((value, context, atom));
+}
+
+#[test]
+fn test_user_data() {
+    struct Database {
+        name: &'static str,
+        dropped: Arc<AtomicBool>,
+    }
+
+    impl Drop for Database {
+        fn drop(&mut self) {
+            self.dropped.store(true, Ordering::SeqCst);
+        }
+    }
+
+    let dropped = Arc::new(AtomicBool::new(false));
+
+    let rt = Runtime::new();
+    assert!(rt.user_data::<Database>().is_none());
+
+    {
+        // the slot stays writable while contexts borrow the runtime
+        let ctx = rt.new_context();
+
+        let previous = rt.set_user_data(Database {
+            name: "primary",
+            dropped: dropped.clone(),
+        });
+        assert!(previous.is_none());
+        assert!(rt.set_user_data(7u32).is_none());
+
+        ctx.define_native_function(ctx.global(), "dbName", |ctx, _, _, _, _| {
+            let db = ctx.runtime().user_data::<Database>().unwrap();
+            ctx.new_string(db.name)
+        })
+        .unwrap();
+
+        let name = ctx.eval_global(None, "dbName()", "test.js", EvalFlags::STRICT).unwrap();
+        assert_eq!(&*ctx.get_string(&name).unwrap(), "primary");
+        assert_eq!(rt.user_data::<u32>().as_deref(), Some(&7));
+        assert_eq!(rt.set_user_data(8u32), Some(7));
+    }
+    assert!(!dropped.load(Ordering::SeqCst));
+
+    drop(rt);
+    assert!(dropped.load(Ordering::SeqCst));
+}
@@ -1,6 +1,6 @@
 use std::sync::{Arc, atomic::AtomicUsize};
 
-use libquickjs::Runtime;
+use libquickjs::{EvalFlags, Runtime, Value};
 
 #[test]
 fn test_jobs() {
@@ -21,3 +21,160 @@ fn test_jobs() {
 
     assert_eq!(call_count.load(std::sync::atomic::Ordering::Relaxed), 100);
 }
+
+#[test]
+fn test_js_jobs() {
+    let rt = Runtime::new();
+    let ctx = rt.new_context();
+
+    let func = ctx
+        .eval_global(
+            None,
+            "globalThis.order = []; Promise.resolve().then(() => order.push('promise')); (a, b) => order.push(a + b)",
+            "test.js",
+            EvalFlags::STRICT,
+        )
+        .unwrap();
+
+    ctx.enqueue_js_job(func, vec![Value::Int32(1), Value::Int32(2)]).unwrap();
+    assert!(ctx.enqueue_js_job(Value::Int32(1), vec![]).is_err());
+
+    rt.execute_pending_jobs();
+
+    let order = ctx
+        .eval_global(None, "order.join(',')", "test.js", EvalFlags::STRICT)
+        .unwrap();
+    assert_eq!(&*ctx.get_string(&order).unwrap(), "promise,3");
+}
+
+#[test]
+fn test_job_panic() {
+    let rt = Runtime::new();
+    let ctx = rt.new_context();
+
+    let call_count = Arc::new(AtomicUsize::new(0));
+
+    ctx.enqueue_job(|_| panic!("job failed")).unwrap();
+    {
+        let call_count = call_count.clone();
+        ctx.enqueue_job(move |_| {
+            call_count.fetch_add(1, std::sync::atomic::Ordering::Relaxed);
+        })
+        .unwrap();
+    }
+
+    // the panic surfaces as an exception of that job only, the next one is still queued
+    let (job_ctx, err) = rt.execute_pending_job().unwrap_err();
+    assert!(job_ctx.is_error(&err));
+    assert_eq!(job_ctx.exception_message(&err).as_deref(), Some("job panicked: job failed"));
+    assert_eq!(call_count.load(std::sync::atomic::Ordering::Relaxed), 0);
+
+    assert!(rt.execute_pending_job().unwrap());
+    assert!(!rt.execute_pending_job().unwrap());
+    assert_eq!(call_count.load(std::sync::atomic::Ordering::Relaxed), 1);
+
+    // draining the whole queue discards the exception and keeps going
+    ctx.enqueue_job(|_| panic!("job failed")).unwrap();
+    {
+        let call_count = call_count.clone();
+        ctx.enqueue_job(move |_| {
+            call_count.fetch_add(1, std::sync::atomic::Ordering::Relaxed);
+        })
+        .unwrap();
+    }
+
+    rt.execute_pending_jobs();
+
+    assert_eq!(call_count.load(std::sync::atomic::Ordering::Relaxed), 2);
+}
+
+#[test]
+fn test_js_job_throw() {
+    let rt = Runtime::new();
+    let ctx = rt.new_context();
+
+    let func = ctx
+        .eval_global(None, "(msg) => { throw new RangeError(msg) }", "test.js", EvalFlags::STRICT)
+        .unwrap();
+    ctx.enqueue_js_job(func, vec![ctx.new_string("bad job").unwrap()]).unwrap();
+
+    let (job_ctx, err) = rt.execute_pending_job().unwrap_err();
+    assert_eq!(job_ctx.exception_message(&err).as_deref(), Some("bad job"));
+    assert!(!rt.execute_pending_job().unwrap());
+}
+
+#[test]
+fn test_job_dropped_with_context() {
+    struct DropGuard(Arc<AtomicUsize>);
+
+    impl Drop for DropGuard {
+        fn drop(&mut self) {
+            self.0.fetch_add(1, std::sync::atomic::Ordering::Relaxed);
+        }
+    }
+
+    let drop_count = Arc::new(AtomicUsize::new(0));
+    let call_count = Arc::new(AtomicUsize::new(0));
+
+    {
+        let rt = Runtime::new();
+        let ctx = rt.new_context();
+
+        let guard = DropGuard(drop_count.clone());
+        let call_count = call_count.clone();
+        ctx.enqueue_job(move |_| {
+            let _guard = guard;
+            call_count.fetch_add(1, std::sync::atomic::Ordering::Relaxed);
+        })
+        .unwrap();
+    }
+
+    // jobs still queued when the runtime goes away never run, the closure is dropped exactly once
+    assert_eq!(call_count.load(std::sync::atomic::Ordering::Relaxed), 0);
+    assert_eq!(drop_count.load(std::sync::atomic::Ordering::Relaxed), 1);
+}
+
+#[test]
+fn test_job_outlives_dropped_context() {
+    let rt = Runtime::new();
+    let call_count = Arc::new(AtomicUsize::new(0));
+
+    {
+        // nothing else refers to a plain context, the queued job alone must keep it alive
+        let ctx = rt.new_plain_context();
+
+        let call_count = call_count.clone();
+        ctx.enqueue_job(move |ctx| {
+            let s = ctx.new_string("alive").unwrap();
+            assert_eq!(&*ctx.get_string(&s).unwrap(), "alive");
+
+            call_count.fetch_add(1, std::sync::atomic::Ordering::Relaxed);
+        })
+        .unwrap();
+    }
+
+    rt.execute_pending_jobs();
+
+    assert_eq!(call_count.load(std::sync::atomic::Ordering::Relaxed), 1);
+}
+
+#[test]
+fn test_drop_context_with_rescheduling_jobs() {
+    let rt = Runtime::new();
+
+    {
+        let ctx = rt.new_context();
+        ctx.eval_global(
+            None,
+            "function f() { Promise.resolve().then(f) } f()",
+            "test.js",
+            EvalFlags::STRICT,
+        )
+        .unwrap();
+    }
+
+    // dropping the context neither runs nor waits for the chain, it is released with the runtime
+    let ctx = rt.new_context();
+    let ret = ctx.eval_global(None, "1 + 1", "test.js", EvalFlags::STRICT).unwrap();
+    assert_eq!(ret, Value::Int32(2));
+}
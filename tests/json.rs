@@ -0,0 +1,113 @@
+use libquickjs::{EvalFlags, Runtime, Value};
+
+#[test]
+fn test_stringify_to_writer() {
+    let rt = Runtime::new();
+    let ctx = rt.new_context();
+
+    let obj = ctx
+        .eval_global(
+            None,
+            r#"({
+                a: 1,
+                b: "hello \"world\"\n",
+                c: [1, undefined, () => 1, null, true],
+                d: { e: undefined, f: Symbol("f"), g: { h: 1.5 } },
+                i: { toJSON(key) { return "to-json:" + key; } },
+                j: new Date(0),
+                k: [new Number(5), new String("x"), new Boolean(false), Object(Symbol("s"))],
+                l: { valueOf() { return 1; } },
+            })"#,
+            "test.js",
+            EvalFlags::STRICT,
+        )
+        .unwrap();
+
+    let mut out = Vec::new();
+    ctx.stringify_to_writer(&obj, &mut out).unwrap();
+
+    let expected = ctx.json_stringify(&obj, &Value::Undefined, &Value::Undefined).unwrap();
+    assert_eq!(std::str::from_utf8(&out).unwrap(), &*ctx.get_string(&expected).unwrap());
+
+    let cyclic = ctx
+        .eval_global(None, "const o = { a: [] }; o.a.push(o); o", "test.js", EvalFlags::STRICT)
+        .unwrap();
+    assert!(ctx.stringify_to_writer(&cyclic, &mut Vec::new()).is_err());
+
+    let boxed_big_int = ctx.eval_global(None, "Object(1n)", "test.js", EvalFlags::STRICT).unwrap();
+    assert!(
+        ctx.json_stringify(&boxed_big_int, &Value::Undefined, &Value::Undefined)
+            .is_err()
+    );
+    assert!(ctx.stringify_to_writer(&boxed_big_int, &mut Vec::new()).is_err());
+}
+
+#[test]
+fn test_stringify_to_writer_nesting() {
+    let rt = Runtime::new();
+    let ctx = rt.new_context();
+
+    let nested = |depth: usize| {
+        let code = format!("(() => {{ let o = 1; for (let i = 0; i < {depth}; i++) o = i % 2 ? [o] : {{ o }}; return o }})()");
+        ctx.eval_global(None, &code, "test.js", EvalFlags::STRICT).unwrap()
+    };
+
+    let shallow = nested(128);
+    let mut out = Vec::new();
+    ctx.stringify_to_writer(&shallow, &mut out).unwrap();
+    let expected = (0..128).fold("1".to_string(), |o, i| {
+        if i % 2 == 1 {
+            format!("[{o}]")
+        } else {
+            format!(r#"{{"o":{o}}}"#)
+        }
+    });
+    assert_eq!(std::str::from_utf8(&out).unwrap(), expected);
+
+    let range_error = ctx.get_property_str(ctx.global(), "RangeError").unwrap();
+    let err = ctx.stringify_to_writer(&nested(129), &mut Vec::new()).unwrap_err();
+    assert!(ctx.is_instance_of(&err, &range_error).unwrap());
+
+    // the limit holds without the engine stack check as well
+    let deep = nested(100_000);
+    rt.set_max_stack_size(None);
+    let err = ctx.stringify_to_writer(&deep, &mut Vec::new()).unwrap_err();
+    assert!(ctx.is_instance_of(&err, &range_error).unwrap());
+
+    // the same object reached twice without a cycle is not circular
+    let shared = ctx
+        .eval_global(None, "const s = { a: 1 }; [s, { s }, [s]]", "test.js", EvalFlags::STRICT)
+        .unwrap();
+    let mut out = Vec::new();
+    ctx.stringify_to_writer(&shared, &mut out).unwrap();
+    assert_eq!(std::str::from_utf8(&out).unwrap(), r#"[{"a":1},{"s":{"a":1}},[{"a":1}]]"#);
+}
+
+#[test]
+fn test_parse_json_ext() {
+    let rt = Runtime::new();
+    let ctx = rt.new_context();
+
+    let json = r#"{
+        // server settings
+        "host": "localhost", /* default host */
+        "path": "/* not a comment */ // nor this",
+        "ports": [80, 443,],
+    }"#;
+
+    assert!(ctx.parse_json_ext(json, "config.json", false).is_err());
+
+    let value = ctx.parse_json_ext(json, "config.json", true).unwrap();
+    let expected = ctx
+        .parse_json(
+            r#"{"host":"localhost","path":"/* not a comment */ // nor this","ports":[80,443]}"#,
+            "config.json",
+        )
+        .unwrap();
+
+    let stringify = |v| {
+        let s = ctx.json_stringify(v, &Value::Undefined, &Value::Undefined).unwrap();
+        ctx.get_string(&s).unwrap().to_string()
+    };
+    assert_eq!(stringify(&value), stringify(&expected));
+}
@@ -38,3 +38,169 @@ fn test_simple_module() {
         _ => panic!("unexpected return type: {:?}", ret),
     }
 }
+
+#[test]
+fn test_import_meta_resolve() {
+    let rt = Runtime::new();
+    let ctx = rt.new_context();
+
+    ctx.eval_module(
+        "globalThis.relative = import.meta.resolve('./assets/logo.png');",
+        "app/main.js",
+        EvalFlags::empty(),
+    )
+    .unwrap();
+
+    let global_obj = ctx.get_global_object();
+    let relative = ctx.get_property_str(&global_obj, "relative").unwrap();
+    assert_eq!(&*ctx.get_string(&relative).unwrap(), "app/assets/logo.png");
+
+    rt.set_module_resolver(|ctx, base, name| {
+        if name.starts_with('.') {
+            Ok(format!("https://example.com/{}/{}", base, name))
+        } else {
+            Err(ctx.new_string(format!("unknown module {}", name))?)
+        }
+    });
+
+    ctx.eval_module(
+        "globalThis.resolved = import.meta.resolve('./logo.png');",
+        "main.js",
+        EvalFlags::empty(),
+    )
+    .unwrap();
+
+    let resolved = ctx.get_property_str(&global_obj, "resolved").unwrap();
+    assert_eq!(&*ctx.get_string(&resolved).unwrap(), "https://example.com/main.js/./logo.png");
+
+    let ret = ctx
+        .eval_module("import.meta.resolve('unknown');", "failed.js", EvalFlags::empty())
+        .unwrap();
+    assert_eq!(ctx.get_promise_state(&ret).unwrap(), PromiseState::Rejected);
+}
+
+#[test]
+fn test_eval_repl() {
+    let rt = Runtime::new();
+    let ctx = rt.new_context();
+
+    assert_eq!(ctx.eval_repl("1 + 2").unwrap(), Value::Int32(3));
+
+    let namespace = ctx.eval_repl("export const answer = await Promise.resolve(42);").unwrap();
+    assert_eq!(ctx.get_property_str(&namespace, "answer").unwrap(), Value::Int32(42));
+
+    let err = ctx
+        .eval_repl("await Promise.reject(new Error('boom')); export {};")
+        .unwrap_err();
+    assert!(ctx.is_error(&err));
+}
+
+#[test]
+fn test_dynamic_import_loader() {
+    let rt = Runtime::new();
+    let ctx = rt.new_context();
+
+    rt.set_module_loader(|ctx, name| match name {
+        "math" => ctx.eval_module("export const x = 42;", name, EvalFlags::COMPILE_ONLY),
+        _ => Err(ctx.new_string(format!("no such module: {}", name)).unwrap()),
+    });
+
+    let promise = ctx
+        .eval_global(None, "import('math').then((m) => m.x * 2)", "script.js", EvalFlags::empty())
+        .unwrap();
+    rt.execute_pending_jobs();
+
+    assert_eq!(ctx.get_promise_state(&promise).unwrap(), PromiseState::Fulfilled);
+    assert_eq!(ctx.get_promise_result(&promise), Value::Int32(84));
+
+    let promise = ctx
+        .eval_global(None, "import('missing')", "script.js", EvalFlags::empty())
+        .unwrap();
+    rt.execute_pending_jobs();
+
+    assert_eq!(ctx.get_promise_state(&promise).unwrap(), PromiseState::Rejected);
+    let reason = ctx.get_promise_result(&promise);
+    assert_eq!(&*ctx.get_string(&reason).unwrap(), "no such module: missing");
+}
+
+#[test]
+fn test_static_import_loader() {
+    let rt = Runtime::new();
+    let ctx = rt.new_context();
+
+    rt.set_module_resolver(|_, _, name| Ok(name.strip_prefix("@lib/").unwrap_or(name).to_string()));
+    rt.set_module_loader(|ctx, name| match name {
+        "math" => ctx.eval_module("export const x = 42;", name, EvalFlags::COMPILE_ONLY),
+        _ => Err(ctx.new_string(format!("no such module: {}", name)).unwrap()),
+    });
+
+    let module = ctx
+        .eval_module(
+            "import { x } from '@lib/math'; globalThis.doubled = x * 2;",
+            "main.js",
+            EvalFlags::COMPILE_ONLY,
+        )
+        .unwrap();
+    ctx.eval_function_async(module).unwrap();
+
+    let doubled = ctx.get_property_str(&ctx.get_global_object(), "doubled").unwrap();
+    assert_eq!(doubled, Value::Int32(84));
+}
+
+#[test]
+fn test_eval_function_async() {
+    let rt = Runtime::new();
+    let ctx = rt.new_context();
+
+    let module = ctx
+        .eval_module(
+            "globalThis.answer = await Promise.resolve(42);",
+            "answer.js",
+            EvalFlags::COMPILE_ONLY,
+        )
+        .unwrap();
+    assert_eq!(ctx.eval_function_async(module).unwrap(), Value::Undefined);
+
+    let answer = ctx.get_property_str(&ctx.get_global_object(), "answer").unwrap();
+    assert_eq!(answer, Value::Int32(42));
+
+    let module = ctx
+        .eval_module("await null; throw new Error('late');", "late.js", EvalFlags::COMPILE_ONLY)
+        .unwrap();
+    let err = ctx.eval_function_async(module).unwrap_err();
+    let message = ctx.get_property_str(&err, "message").unwrap();
+    assert_eq!(&*ctx.get_string(&message).unwrap(), "late");
+}
+
+#[test]
+fn test_merge_namespaces() {
+    let rt = Runtime::new();
+    let ctx = rt.new_context();
+
+    let math = ctx
+        .eval_module(
+            "export const add = (a, b) => a + b; export const version = 1;",
+            "math.js",
+            EvalFlags::COMPILE_ONLY,
+        )
+        .unwrap();
+    ctx.eval_function_async(math.clone()).unwrap();
+
+    let text = ctx
+        .eval_repl("export const upper = (s) => s.toUpperCase(); export const version = 2;")
+        .unwrap();
+
+    let api = ctx.new_object(None).unwrap();
+    ctx.merge_namespaces(&api, &[math, text]).unwrap();
+    ctx.set_property_str(ctx.global(), "api", api).unwrap();
+
+    let ret = ctx
+        .eval_global(
+            None,
+            "`${api.add(1, 2)}:${api.upper('x')}:${api.version}`",
+            "test.js",
+            EvalFlags::STRICT,
+        )
+        .unwrap();
+    assert_eq!(&*ctx.get_string(&ret).unwrap(), "3:X:2");
+}
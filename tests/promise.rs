@@ -0,0 +1,194 @@
+use std::sync::{Arc, Mutex};
+
+use libquickjs::{EvalFlags, PromiseState, Runtime, Value};
+
+#[test]
+fn test_settled_promise() {
+    let rt = Runtime::new();
+    let ctx = rt.new_context();
+
+    let resolved = ctx.resolved_promise(Value::Int32(42)).unwrap();
+    assert!(ctx.is_promise(&resolved));
+    assert_eq!(ctx.get_promise_state(&resolved).unwrap(), PromiseState::Fulfilled);
+    assert_eq!(ctx.get_promise_result(&resolved), Value::Int32(42));
+
+    let reason = ctx.new_string("failed").unwrap();
+    let rejected = ctx.rejected_promise(reason).unwrap();
+    assert_eq!(ctx.get_promise_state(&rejected).unwrap(), PromiseState::Rejected);
+    assert_eq!(
+        ctx.get_string(&ctx.get_promise_result(&rejected)).unwrap().to_string(),
+        "failed"
+    );
+}
+
+#[test]
+fn test_promise_capability_global() {
+    let rt = Runtime::new();
+    let ctx = rt.new_context();
+
+    let (promise, resolve, reject) = ctx.new_promise_capability_global().unwrap();
+
+    // resolving functions are sendable to other threads as globals
+    let (resolve, _reject) = std::thread::spawn(move || (resolve, reject)).join().unwrap();
+
+    let promise = promise.to_local(&rt).unwrap();
+    assert_eq!(ctx.get_promise_state(&promise).unwrap(), PromiseState::Pending);
+
+    let resolve = resolve.to_local(&rt).unwrap();
+    ctx.call(&resolve, &Value::Undefined, &[Value::Int32(7)]).unwrap();
+    rt.execute_pending_jobs();
+
+    assert_eq!(ctx.get_promise_state(&promise).unwrap(), PromiseState::Fulfilled);
+    assert_eq!(ctx.get_promise_result(&promise), Value::Int32(7));
+}
+
+#[test]
+fn test_promise_then() {
+    let rt = Runtime::new();
+    let ctx = rt.new_context();
+
+    let seen = Arc::new(Mutex::new(Vec::new()));
+
+    let (promise, (resolve, _)) = ctx.new_promise_capability().unwrap();
+    let fulfilled = seen.clone();
+    let chained = ctx
+        .promise_then(
+            &promise,
+            move |ctx, value| {
+                fulfilled.lock().unwrap().push(format!("ok:{}", ctx.to_int32(value)?));
+                Ok(Value::Int32(ctx.to_int32(value)? * 2))
+            },
+            |_, _| Ok(Value::Undefined),
+        )
+        .unwrap();
+
+    ctx.call(&resolve, &Value::Undefined, &[Value::Int32(21)]).unwrap();
+    rt.execute_pending_jobs();
+
+    assert_eq!(ctx.get_promise_state(&chained).unwrap(), PromiseState::Fulfilled);
+    assert_eq!(ctx.get_promise_result(&chained), Value::Int32(42));
+
+    let rejected = ctx.rejected_promise(ctx.new_string("boom").unwrap()).unwrap();
+    let failed = seen.clone();
+    ctx.promise_then(
+        &rejected,
+        |_, _| Ok(Value::Undefined),
+        move |ctx, reason| {
+            failed.lock().unwrap().push(format!("err:{}", &*ctx.get_string(reason)?));
+            Ok(Value::Undefined)
+        },
+    )
+    .unwrap();
+    rt.execute_pending_jobs();
+
+    assert_eq!(*seen.lock().unwrap(), vec!["ok:21".to_string(), "err:boom".to_string()]);
+    assert!(
+        ctx.promise_then(&Value::Int32(1), |_, _| Ok(Value::Undefined), |_, _| Ok(Value::Undefined))
+            .is_err()
+    );
+}
+
+#[test]
+fn test_invoke_and_settle() {
+    let rt = Runtime::new();
+    let ctx = rt.new_context();
+
+    let store = ctx
+        .eval_global(
+            None,
+            r#"({
+                async save(n) { await null; return n + 1; },
+                async fail() { await null; throw "rejected"; },
+                size() { return 3; },
+            })"#,
+            "test.js",
+            EvalFlags::STRICT,
+        )
+        .unwrap();
+
+    assert_eq!(
+        ctx.invoke_and_settle(&store, "save", &[Value::Int32(41)]).unwrap(),
+        Value::Int32(42)
+    );
+    assert_eq!(ctx.invoke_and_settle(&store, "size", &[]).unwrap(), Value::Int32(3));
+
+    let reason = ctx.invoke_and_settle(&store, "fail", &[]).unwrap_err();
+    assert_eq!(&*ctx.get_string(&reason).unwrap(), "rejected");
+}
+
+#[test]
+fn test_promise_rejection_tracker() {
+    let rt = Runtime::new();
+    let ctx = rt.new_context();
+
+    let seen = Arc::new(Mutex::new(Vec::new()));
+    rt.set_promise_rejection_tracker({
+        let seen = seen.clone();
+        move |ctx, promise, reason, is_handled| {
+            assert!(ctx.is_promise(promise));
+            let message = ctx.get_property_str(reason, "message").unwrap();
+            seen.lock()
+                .unwrap()
+                .push((ctx.get_string(&message).unwrap().to_string(), is_handled));
+        }
+    });
+
+    ctx.eval_global(
+        None,
+        "globalThis.p = Promise.reject(new Error('x'))",
+        "test.js",
+        EvalFlags::STRICT,
+    )
+    .unwrap();
+    rt.execute_pending_jobs();
+    assert_eq!(*seen.lock().unwrap(), vec![("x".to_string(), false)]);
+
+    ctx.eval_global(None, "p.catch(() => {})", "test.js", EvalFlags::STRICT)
+        .unwrap();
+    rt.execute_pending_jobs();
+    assert_eq!(seen.lock().unwrap().last(), Some(&("x".to_string(), true)));
+}
+
+#[test]
+fn test_resolve_promise() {
+    let rt = Runtime::new();
+    let ctx = rt.new_context();
+
+    let promise = ctx
+        .eval_global(None, "(async () => 1 + 2)()", "test.js", EvalFlags::STRICT)
+        .unwrap();
+    assert_eq!(ctx.resolve_promise(&promise).unwrap(), Value::Int32(3));
+
+    let promise = ctx
+        .eval_global(None, "(async () => { await null; throw 4; })()", "test.js", EvalFlags::STRICT)
+        .unwrap();
+    assert_eq!(ctx.resolve_promise(&promise).unwrap_err(), Value::Int32(4));
+
+    let (pending, _) = ctx.new_promise_capability().unwrap();
+    assert!(ctx.resolve_promise(&pending).is_err());
+
+    assert!(ctx.resolve_promise(&Value::Int32(1)).is_err());
+}
+
+#[test]
+fn test_promise_rejection_tracker_replaces_itself() {
+    let rt = Runtime::new();
+    let ctx = rt.new_context();
+
+    let seen = Arc::new(Mutex::new(Vec::new()));
+    rt.set_promise_rejection_tracker({
+        let seen = seen.clone();
+        move |ctx, _, _, _| {
+            seen.lock().unwrap().push("first");
+
+            let seen = seen.clone();
+            ctx.runtime()
+                .set_promise_rejection_tracker(move |_, _, _, _| seen.lock().unwrap().push("second"));
+        }
+    });
+
+    ctx.eval_global(None, "Promise.reject(1); Promise.reject(2)", "test.js", EvalFlags::STRICT)
+        .unwrap();
+    rt.execute_pending_jobs();
+    assert_eq!(*seen.lock().unwrap(), vec!["first", "second"]);
+}
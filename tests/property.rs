@@ -1,4 +1,9 @@
-use libquickjs::{EvalFlags, GetOwnAtomFlags, Runtime, Value};
+use std::sync::{
+    Arc, Mutex,
+    atomic::{AtomicI32, Ordering},
+};
+
+use libquickjs::{Class, EvalFlags, GetOwnAtomFlags, NamespaceEntry, PropertyDescriptorFlags, Runtime, Value};
 
 #[test]
 fn test_enum_property() {
@@ -28,3 +33,401 @@ fn test_enum_property() {
         }
     }
 }
+
+#[test]
+fn test_get_path() {
+    let rt = Runtime::new();
+    let ctx = rt.new_context();
+
+    let config = ctx
+        .eval_global(
+            None,
+            r#"({ server: { http: { port: 8080 }, tls: null } })"#,
+            "script.js",
+            EvalFlags::empty(),
+        )
+        .unwrap();
+
+    assert_eq!(ctx.get_path(&config, "server.http.port").unwrap(), Value::Int32(8080));
+    assert_eq!(ctx.get_path(&config, "server.tls").unwrap(), Value::Null);
+    assert_eq!(ctx.get_path(&config, "server.tls.cert").unwrap(), Value::Undefined);
+    assert_eq!(ctx.get_path(&config, "client.timeout.ms").unwrap(), Value::Undefined);
+}
+
+#[test]
+fn test_define_property_value_key() {
+    let rt = Runtime::new();
+    let ctx = rt.new_context();
+
+    let obj = ctx.new_object(None).unwrap();
+    let symbol = ctx.new_symbol("tag", false).unwrap();
+
+    for (key, value) in [
+        (symbol.clone(), Value::Int32(1)),
+        (ctx.new_string("name").unwrap(), Value::Int32(2)),
+        (Value::Int32(3), Value::Int32(3)),
+    ] {
+        ctx.define_property_value_key(&obj, &key, value, PropertyDescriptorFlags::ENUMERABLE)
+            .unwrap();
+    }
+
+    let sym_atom = ctx.value_to_atom(&symbol).unwrap();
+    assert_eq!(ctx.get_property(&obj, &sym_atom).unwrap(), Value::Int32(1));
+    assert_eq!(ctx.get_property_str(&obj, "name").unwrap(), Value::Int32(2));
+    assert_eq!(ctx.get_property_uint32(&obj, 3).unwrap(), Value::Int32(3));
+}
+
+#[test]
+fn test_define_property_value_int64() {
+    let rt = Runtime::new();
+    let ctx = rt.new_context();
+
+    let arr = ctx.new_array().unwrap();
+    for idx in [1, 1 << 33, -1] {
+        ctx.define_property_value_int64(&arr, idx, Value::Int32(idx as i32 | 1), PropertyDescriptorFlags::ENUMERABLE)
+            .unwrap();
+    }
+
+    ctx.set_property_str(&ctx.get_global_object(), "arr", arr).unwrap();
+    let ret = ctx
+        .eval_global(
+            None,
+            r#"[arr.length, arr[8589934592], arr[-1], Object.getOwnPropertyDescriptor(arr, "8589934592").writable].join()"#,
+            "test.js",
+            EvalFlags::STRICT,
+        )
+        .unwrap();
+    assert_eq!(&*ctx.get_string(&ret).unwrap(), "2,1,-1,false");
+}
+
+#[test]
+fn test_array_to_vec() {
+    let rt = Runtime::new();
+    let ctx = rt.new_context();
+
+    let array = ctx
+        .eval_global(None, "[1, 'two', , null]", "script.js", EvalFlags::empty())
+        .unwrap();
+    let values = ctx.array_to_vec(&array).unwrap();
+
+    assert_eq!(values.len(), 4);
+    assert_eq!(values[0], Value::Int32(1));
+    assert_eq!(&*ctx.get_string(&values[1]).unwrap(), "two");
+    assert_eq!(values[2], Value::Undefined);
+    assert_eq!(values[3], Value::Null);
+
+    let obj = ctx.new_object(None).unwrap();
+    assert!(ctx.array_to_vec(&obj).is_err());
+}
+
+#[test]
+fn test_array_slice_concat() {
+    let rt = Runtime::new();
+    let ctx = rt.new_context();
+
+    let a = ctx
+        .eval_global(None, "[1, 2, 3, 4]", "script.js", EvalFlags::empty())
+        .unwrap();
+    let b = ctx.eval_global(None, "[5, 6]", "script.js", EvalFlags::empty()).unwrap();
+
+    let slice = ctx.array_slice(&a, 1, Some(3)).unwrap();
+    assert_eq!(ctx.array_to_vec(&slice).unwrap(), vec![Value::Int32(2), Value::Int32(3)]);
+
+    let tail = ctx.array_slice(&a, -1, None).unwrap();
+    assert_eq!(ctx.array_to_vec(&tail).unwrap(), vec![Value::Int32(4)]);
+
+    let joined = ctx.array_concat(&tail, &b).unwrap();
+    assert_eq!(
+        ctx.array_to_vec(&joined).unwrap(),
+        vec![Value::Int32(4), Value::Int32(5), Value::Int32(6)]
+    );
+
+    let obj = ctx.new_object(None).unwrap();
+    assert!(ctx.array_slice(&obj, 0, None).is_err());
+    assert!(ctx.array_concat(&obj, &b).is_err());
+}
+
+#[test]
+fn test_truncate_array() {
+    let rt = Runtime::new();
+    let ctx = rt.new_context();
+
+    let array = ctx
+        .eval_global(None, "[1, 2, 3, 4]", "script.js", EvalFlags::empty())
+        .unwrap();
+
+    ctx.truncate_array(&array, 2).unwrap();
+    assert_eq!(ctx.array_to_vec(&array).unwrap(), vec![Value::Int32(1), Value::Int32(2)]);
+    assert!(!ctx.has_property(&array, &ctx.new_atom_uint32(2).unwrap()).unwrap());
+
+    // never grows
+    ctx.truncate_array(&array, 10).unwrap();
+    assert_eq!(ctx.get_length(&array).unwrap(), 2);
+
+    ctx.set_length(&array, 0).unwrap();
+    assert!(!ctx.has_property(&array, &ctx.new_atom_uint32(0).unwrap()).unwrap());
+
+    let obj = ctx.new_object(None).unwrap();
+    assert!(ctx.truncate_array(&obj, 0).is_err());
+}
+
+#[test]
+fn test_define_constant() {
+    let rt = Runtime::new();
+    let ctx = rt.new_context();
+
+    let host = ctx.new_object(None).unwrap();
+    ctx.define_constant(&host, "VERSION", Value::Int32(3)).unwrap();
+
+    let global_obj = ctx.get_global_object();
+    ctx.set_property_str(&global_obj, "host", host.clone()).unwrap();
+
+    let ret = ctx
+        .eval_global(
+            None,
+            r#"host.VERSION = 4; delete host.VERSION; `${host.VERSION}:${Object.keys(host)}`"#,
+            "script.js",
+            EvalFlags::empty(),
+        )
+        .unwrap();
+    assert_eq!(&*ctx.get_string(&ret).unwrap(), "3:VERSION");
+
+    assert!(ctx.define_constant(&host, "VERSION", Value::Int32(5)).is_err());
+}
+
+#[test]
+fn test_own_atoms_iter() {
+    let rt = Runtime::new();
+    let ctx = rt.new_context();
+
+    let obj = ctx
+        .eval_global(
+            None,
+            r#"const big = {}; for (let i = 0; i < 1000; i++) big["key" + i] = i; big"#,
+            "script.js",
+            EvalFlags::empty(),
+        )
+        .unwrap();
+
+    let mut iter = ctx.own_atoms_iter(&obj, GetOwnAtomFlags::STRING_MASK).unwrap();
+    assert_eq!(iter.len(), 1000);
+
+    let first = iter.next().unwrap();
+    assert!(first.is_enumerable);
+    assert_eq!(&*ctx.get_string(&ctx.atom_to_string(&first.atom).unwrap()).unwrap(), "key0");
+    assert_eq!(iter.len(), 999);
+
+    // remaining atoms are released when the iterator is dropped early
+    let taken = iter.take(10).count();
+    assert_eq!(taken, 10);
+
+    let count = ctx.own_atoms_iter(&obj, GetOwnAtomFlags::STRING_MASK).unwrap().count();
+    assert_eq!(count, 1000);
+}
+
+#[test]
+fn test_own_atoms_iter_outlives_context() {
+    let rt = Runtime::new();
+
+    let mut iter = {
+        let ctx = rt.new_context();
+        let obj = ctx
+            .eval_global(None, "({ a: 1, b: 2, c: 3 })", "script.js", EvalFlags::empty())
+            .unwrap();
+
+        ctx.own_atoms_iter(&obj, GetOwnAtomFlags::STRING_MASK).unwrap()
+    };
+
+    assert!(iter.next().is_some());
+    drop(iter);
+}
+
+#[test]
+fn test_own_key_count() {
+    let rt = Runtime::new();
+    let ctx = rt.new_context();
+
+    let obj = ctx
+        .eval_global(
+            None,
+            r#"const o = { a: 1, b: 2, [Symbol('s')]: 3 }; Object.defineProperty(o, 'hidden', { value: 4 }); o"#,
+            "script.js",
+            EvalFlags::empty(),
+        )
+        .unwrap();
+
+    let enumerable = GetOwnAtomFlags::STRING_MASK | GetOwnAtomFlags::ENUM_ONLY;
+    assert_eq!(ctx.own_key_count(&obj, enumerable).unwrap(), 2);
+    assert_eq!(ctx.own_key_count(&obj, GetOwnAtomFlags::STRING_MASK).unwrap(), 3);
+    assert_eq!(
+        ctx.own_key_count(&obj, GetOwnAtomFlags::STRING_MASK | GetOwnAtomFlags::SYMBOL_MASK)
+            .unwrap(),
+        4
+    );
+}
+
+#[test]
+fn test_get_property_as() {
+    let rt = Runtime::new();
+    let ctx = rt.new_context();
+
+    let obj = ctx
+        .eval_global(
+            None,
+            r#"({ count: 3, ratio: 0.5, enabled: true })"#,
+            "script.js",
+            EvalFlags::empty(),
+        )
+        .unwrap();
+
+    let count: i32 = ctx.get_property_as(&obj, &ctx.new_atom("count").unwrap()).unwrap();
+    let ratio: f64 = ctx.get_property_as(&obj, &ctx.new_atom("ratio").unwrap()).unwrap();
+    let enabled: bool = ctx.get_property_as(&obj, &ctx.new_atom("enabled").unwrap()).unwrap();
+    assert_eq!((count, ratio, enabled), (3, 0.5, true));
+
+    let mismatch = ctx.get_property_as::<i32>(&obj, &ctx.new_atom("enabled").unwrap());
+    assert!(ctx.is_error(&mismatch.unwrap_err()));
+}
+
+#[test]
+fn test_expose_struct() {
+    struct Counter {
+        hits: Arc<AtomicI32>,
+    }
+
+    impl Class for Counter {
+        const NAME: &'static str = "Counter";
+    }
+
+    let rt = Runtime::new();
+    let ctx = rt.new_context();
+
+    let hits = Arc::new(AtomicI32::new(1));
+    let counter = ctx
+        .expose_struct(
+            Counter { hits: hits.clone() },
+            &[
+                ("hits", |_, c| Ok(Value::Int32(c.hits.load(Ordering::SeqCst)))),
+                ("label", |ctx, _| ctx.new_string("counter")),
+            ],
+        )
+        .unwrap();
+    ctx.set_property_str(ctx.global(), "counter", counter).unwrap();
+
+    let read = || {
+        ctx.eval_global(None, "`${counter.label}:${counter.hits}`", "test.js", EvalFlags::STRICT)
+            .unwrap()
+    };
+    assert_eq!(&*ctx.get_string(&read()).unwrap(), "counter:1");
+
+    hits.store(5, Ordering::SeqCst);
+    assert_eq!(&*ctx.get_string(&read()).unwrap(), "counter:5");
+
+    let ret = ctx
+        .eval_global(
+            None,
+            "try { Reflect.apply(Object.getOwnPropertyDescriptor(counter, 'hits').get, {}, []) } catch (e) { `${e.name}: ${e.message}` }",
+            "test.js",
+            EvalFlags::STRICT,
+        )
+        .unwrap();
+    assert_eq!(
+        &*ctx.get_string(&ret).unwrap(),
+        "TypeError: Counter getter called on incompatible receiver"
+    );
+}
+
+#[test]
+fn test_populate() {
+    let rt = Runtime::new();
+    let ctx = rt.new_context();
+
+    let ns = ctx.new_object(None).unwrap();
+    ctx.populate(
+        &ns,
+        &[
+            NamespaceEntry::Function {
+                name: "add",
+                arity: 2,
+                f: |ctx, _, _, args, _| {
+                    let a = ctx.to_int32(args.first().unwrap_or(&Value::Undefined))?;
+                    let b = ctx.to_int32(args.get(1).unwrap_or(&Value::Undefined))?;
+                    Ok(Value::Int32(a + b))
+                },
+            },
+            NamespaceEntry::Value {
+                name: "VERSION",
+                value: Value::Int32(7),
+            },
+            NamespaceEntry::Getter {
+                name: "now",
+                g: |_, _, _, _, _| Ok(Value::Int32(1234)),
+            },
+        ],
+    )
+    .unwrap();
+    ctx.set_property_str(ctx.global(), "ns", ns).unwrap();
+
+    let ret = ctx
+        .eval_global(
+            None,
+            "`${ns.add(1, 2)}:${ns.add.length}:${ns.add.name}:${ns.VERSION}:${ns.now}`",
+            "test.js",
+            EvalFlags::STRICT,
+        )
+        .unwrap();
+    assert_eq!(&*ctx.get_string(&ret).unwrap(), "3:2:add:7:1234");
+}
+
+#[test]
+fn test_new_frozen_object() {
+    let rt = Runtime::new();
+    let ctx = rt.new_context();
+
+    let record = ctx
+        .new_frozen_object(&[("id", Value::Int32(1)), ("name", ctx.new_string("first").unwrap())])
+        .unwrap();
+    ctx.set_property_str(ctx.global(), "record", record).unwrap();
+
+    let ret = ctx
+        .eval_global(
+            None,
+            r#"
+            const errors = [];
+            for (const mutate of [() => { record.id = 2 }, () => { record.extra = 1 }, () => { delete record.name }]) {
+                try { mutate() } catch (e) { errors.push(e instanceof TypeError) }
+            }
+            `${Object.isFrozen(record)}:${errors.join()}:${Object.keys(record).join()}:${record.id}`
+            "#,
+            "test.js",
+            EvalFlags::STRICT,
+        )
+        .unwrap();
+    assert_eq!(&*ctx.get_string(&ret).unwrap(), "true:true,true,true:id,name:1");
+}
+
+#[test]
+fn test_define_reactive_property() {
+    let rt = Runtime::new();
+    let ctx = rt.new_context();
+
+    let counter = Arc::new(Mutex::new(1));
+    ctx.define_reactive_property(ctx.global(), "counter", counter.clone())
+        .unwrap();
+
+    let ret = ctx
+        .eval_global(None, "counter += 41; counter", "test.js", EvalFlags::STRICT)
+        .unwrap();
+    assert_eq!(ret, Value::Int32(42));
+    assert_eq!(*counter.lock().unwrap(), 42);
+
+    *counter.lock().unwrap() = 7;
+    let ret = ctx.eval_global(None, "counter", "test.js", EvalFlags::STRICT).unwrap();
+    assert_eq!(ret, Value::Int32(7));
+
+    assert!(
+        ctx.eval_global(None, "counter = 'seven'", "test.js", EvalFlags::STRICT)
+            .is_err()
+    );
+    assert_eq!(*counter.lock().unwrap(), 7);
+}
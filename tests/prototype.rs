@@ -1,4 +1,4 @@
-use libquickjs::{Class, Runtime, Value};
+use libquickjs::{Class, EvalFlags, NativeFunction, Runtime, Value};
 
 #[test]
 fn test_set_get_prototype() {
@@ -15,3 +15,397 @@ fn test_set_get_prototype() {
 
     assert_eq!(null_prototype, Value::Null);
 }
+
+#[test]
+fn test_lockdown() {
+    let rt = Runtime::new();
+
+    let ctx = rt.new_context();
+    ctx.lockdown().unwrap();
+
+    let ret = ctx
+        .eval_global(
+            None,
+            r#"
+            Array.prototype.push = null;
+            Object.prototype.polluted = true;
+            Object.isFrozen(Array.prototype) && Object.isFrozen(Object) && Object.isFrozen(Object.getPrototypeOf(Int8Array))
+                && [].push === Array.prototype.push && ({}).polluted === undefined
+                && [1, 2].map(x => x * 2).join(",") === "2,4"
+            "#,
+            "test.js",
+            EvalFlags::empty(),
+        )
+        .unwrap();
+    assert_eq!(ret, Value::Bool(true));
+
+    assert!(
+        ctx.eval_global(None, "Array.prototype.push = null", "test.js", EvalFlags::STRICT)
+            .is_err()
+    );
+}
+
+#[test]
+fn test_is_instance_of_class() {
+    struct Foo;
+
+    impl Class for Foo {
+        const NAME: &'static str = "Foo";
+    }
+
+    struct Bar;
+
+    impl Class for Bar {
+        const NAME: &'static str = "Bar";
+    }
+
+    let rt = Runtime::new();
+
+    let ctx = rt.new_context();
+    let foo = ctx.new_object_class(Foo, None).unwrap();
+    let bar = ctx.new_object_class(Bar, None).unwrap();
+    let plain = ctx.new_object(None).unwrap();
+
+    assert!(ctx.is_instance_of_class::<Foo>(&foo));
+    assert!(!ctx.is_instance_of_class::<Foo>(&bar));
+    assert!(ctx.is_instance_of_class::<Bar>(&bar));
+    assert!(!ctx.is_instance_of_class::<Foo>(&plain));
+    assert!(!ctx.is_instance_of_class::<Foo>(&Value::Int32(1)));
+
+    struct Baz;
+
+    impl Class for Baz {
+        const NAME: &'static str = "Baz";
+    }
+
+    // asking about a class that was never registered does not allocate an id for it
+    let next_class_id = || unsafe { rquickjs_sys::JS_NewClassID(rt.as_raw().as_ptr(), &mut 0) };
+    let before = next_class_id();
+    assert!(!ctx.is_instance_of_class::<Baz>(&plain));
+    assert!(!ctx.is_instance_of_class::<Baz>(&foo));
+    assert_eq!(next_class_id(), before + 1);
+}
+
+#[test]
+fn test_new_null_proto_object() {
+    let rt = Runtime::new();
+
+    let ctx = rt.new_context();
+    let obj = ctx.new_null_proto_object().unwrap();
+
+    assert_eq!(ctx.get_prototype(&obj).unwrap(), Value::Null);
+    assert_eq!(ctx.get_property_str(&obj, "toString").unwrap(), Value::Undefined);
+
+    let plain = ctx.new_object(None).unwrap();
+    assert_ne!(ctx.get_prototype(&plain).unwrap(), Value::Null);
+}
+
+#[test]
+fn test_class_name_of() {
+    struct Handle;
+
+    impl Class for Handle {
+        const NAME: &'static str = "Handle";
+    }
+
+    let rt = Runtime::new();
+
+    let ctx = rt.new_context();
+    let handle = ctx.new_object_class(Handle, None).unwrap();
+    let plain = ctx.new_object(None).unwrap();
+
+    assert_eq!(ctx.class_name_of(&handle), Some("Handle"));
+    assert_eq!(ctx.class_name_of(&plain), None);
+    assert_eq!(ctx.class_name_of(&Value::Int32(1)), None);
+}
+
+#[test]
+fn test_instance_of_has_instance() {
+    let rt = Runtime::new();
+
+    let ctx = rt.new_context();
+    let even = ctx
+        .eval_global(
+            None,
+            "(class Even { static [Symbol.hasInstance](n) { return n % 2 === 0; } })",
+            "test.js",
+            EvalFlags::STRICT,
+        )
+        .unwrap();
+
+    assert!(ctx.instance_of(&Value::Int32(4), &even).unwrap());
+    assert!(!ctx.instance_of(&Value::Int32(3), &even).unwrap());
+    assert!(ctx.is_instance_of(&Value::Int32(4), &even).unwrap());
+
+    assert!(!ctx.ordinary_has_instance(&even, &Value::Int32(4)).unwrap());
+    let instance = ctx.call_constructor(&even, None, &[]).unwrap();
+    assert!(ctx.ordinary_has_instance(&even, &instance).unwrap());
+
+    let array = ctx.get_property_str(&ctx.get_global_object(), "Array").unwrap();
+    let list = ctx.new_array().unwrap();
+    assert!(ctx.instance_of(&list, &array).unwrap());
+    assert!(ctx.instance_of(&Value::Int32(1), &Value::Int32(1)).is_err());
+
+    // a shadowed global Symbol does not change which method is looked up
+    ctx.eval_global(
+        None,
+        "globalThis.Symbol = { hasInstance: 'length' }",
+        "test.js",
+        EvalFlags::STRICT,
+    )
+    .unwrap();
+    assert!(ctx.instance_of(&Value::Int32(4), &even).unwrap());
+    assert!(ctx.ordinary_has_instance(&even, &instance).unwrap());
+}
+
+#[test]
+fn test_class_name_override() {
+    struct HostObject {
+        kind: &'static str,
+    }
+
+    impl Class for HostObject {
+        const NAME: &'static str = "HostObject";
+
+        fn class_name(&self) -> &str {
+            self.kind
+        }
+    }
+
+    let rt = Runtime::new();
+
+    let ctx = rt.new_context();
+
+    // the tag does not go through the global Symbol
+    ctx.eval_global(None, "globalThis.Symbol = undefined", "test.js", EvalFlags::STRICT)
+        .unwrap();
+
+    let file = ctx.new_object_class(HostObject { kind: "File" }, None).unwrap();
+    let socket = ctx.new_object_class(HostObject { kind: "Socket" }, None).unwrap();
+    let plain = ctx.new_object_class(HostObject { kind: "HostObject" }, None).unwrap();
+
+    let to_string = ctx
+        .eval_global(None, "(o) => Object.prototype.toString.call(o)", "test.js", EvalFlags::STRICT)
+        .unwrap();
+    let describe = |obj: &Value| {
+        let s = ctx.call(&to_string, &Value::Undefined, std::slice::from_ref(obj)).unwrap();
+        ctx.get_string(&s).unwrap().to_string()
+    };
+
+    assert_eq!(describe(&file), "[object File]");
+    assert_eq!(describe(&socket), "[object Socket]");
+    assert_eq!(describe(&plain), "[object Function]");
+
+    assert!(ctx.is_instance_of_class::<HostObject>(&file));
+    assert!(ctx.is_instance_of_class::<HostObject>(&socket));
+
+    assert_eq!(ctx.class_name_of(&file), Some("File"));
+    assert_eq!(ctx.class_name_of(&socket), Some("Socket"));
+    assert_eq!(ctx.class_name_of(&plain), Some("HostObject"));
+
+    // the tag lives once on the class prototype
+    let proto = ctx.get_prototype(&file).unwrap();
+    assert_eq!(proto, ctx.get_prototype(&socket).unwrap());
+    let own = ctx
+        .eval_global(
+            None,
+            "(o) => Object.getOwnPropertySymbols(o).length",
+            "test.js",
+            EvalFlags::STRICT,
+        )
+        .unwrap();
+    let own_count = |obj: &Value| ctx.call(&own, &Value::Undefined, std::slice::from_ref(obj)).unwrap();
+    assert_eq!(own_count(&file), Value::Int32(0));
+    assert_eq!(own_count(&proto), Value::Int32(1));
+
+    // a caller supplied prototype is left alone, the instance carries the tag itself
+    let custom = ctx.new_object(None).unwrap();
+    let pipe = ctx.new_object_class(HostObject { kind: "Pipe" }, Some(&custom)).unwrap();
+    assert_eq!(describe(&pipe), "[object Pipe]");
+    assert_eq!(own_count(&custom), Value::Int32(0));
+}
+
+#[test]
+fn test_class_name_tag_lookup_throws() {
+    struct HostObject;
+
+    impl Class for HostObject {
+        const NAME: &'static str = "HostObject";
+
+        fn class_name(&self) -> &str {
+            "File"
+        }
+    }
+
+    let rt = Runtime::new();
+
+    let ctx = rt.new_context();
+
+    let proto = ctx
+        .eval_global(
+            None,
+            "new Proxy({}, { getOwnPropertyDescriptor() { throw new Error('trap') } })",
+            "test.js",
+            EvalFlags::STRICT,
+        )
+        .unwrap();
+    ctx.set_class_proto::<HostObject>(proto);
+
+    let Err(err) = ctx.new_object_class(HostObject, None) else {
+        panic!("tag lookup error swallowed");
+    };
+    let message = ctx.get_property_str(&err, "message").unwrap();
+    assert_eq!(&*ctx.get_string(&message).unwrap(), "trap");
+
+    // nothing is left pending on the context
+    assert_eq!(
+        ctx.eval_global(None, "1 + 1", "test.js", EvalFlags::STRICT).unwrap(),
+        Value::Int32(2)
+    );
+}
+
+#[test]
+fn test_new_instance() {
+    struct Config {
+        name: &'static str,
+        retries: std::sync::atomic::AtomicU32,
+    }
+
+    impl Class for Config {
+        const NAME: &'static str = "Config";
+    }
+
+    let rt = Runtime::new();
+    let ctx = rt.new_context();
+
+    let (obj, config) = ctx
+        .new_instance(Config {
+            name: "primary",
+            retries: Default::default(),
+        })
+        .unwrap();
+    assert_eq!(config.name, "primary");
+
+    config.retries.store(3, std::sync::atomic::Ordering::SeqCst);
+
+    let stored = ctx.get_class_opaque::<Config>(&obj).unwrap();
+    assert!(std::ptr::eq(stored, config));
+    assert_eq!(stored.retries.load(std::sync::atomic::Ordering::SeqCst), 3);
+}
+
+#[test]
+fn test_define_method() {
+    struct Point {
+        x: i32,
+        y: i32,
+    }
+
+    impl Class for Point {
+        const NAME: &'static str = "Point";
+    }
+
+    let rt = Runtime::new();
+    let ctx = rt.new_context();
+
+    let proto = ctx.new_object(None).unwrap();
+    ctx.define_method::<Point, _>(&proto, "sum", |_, point, args| {
+        let extra = match args.first() {
+            Some(Value::Int32(v)) => *v,
+            _ => 0,
+        };
+        Ok(Value::Int32(point.x + point.y + extra))
+    })
+    .unwrap();
+    ctx.set_class_proto::<Point>(proto);
+
+    let point = ctx.new_object_class(Point { x: 1, y: 2 }, None).unwrap();
+    ctx.set_property_str(ctx.global(), "point", point).unwrap();
+
+    let ret = ctx
+        .eval_global(
+            None,
+            "`${point.sum()}:${point.sum(10)}:${point.sum.name}`",
+            "test.js",
+            EvalFlags::STRICT,
+        )
+        .unwrap();
+    assert_eq!(&*ctx.get_string(&ret).unwrap(), "3:13:sum");
+
+    let ret = ctx
+        .eval_global(
+            None,
+            "try { Reflect.apply(point.sum, {}, []) } catch (e) { `${e.name}: ${e.message}` }",
+            "test.js",
+            EvalFlags::STRICT,
+        )
+        .unwrap();
+    assert_eq!(
+        &*ctx.get_string(&ret).unwrap(),
+        "TypeError: Point method called on incompatible receiver"
+    );
+}
+
+#[test]
+fn test_new_instance_of() {
+    struct Counter(i32);
+
+    impl Class for Counter {
+        const NAME: &'static str = "Counter";
+    }
+
+    let rt = Runtime::new();
+    let ctx = rt.new_context();
+
+    let ctor = ctx
+        .eval_global(None, "(function Counter() {})", "test.js", EvalFlags::STRICT)
+        .unwrap();
+
+    let obj = ctx.new_instance_of(&ctor, Counter(7)).unwrap();
+    assert!(ctx.instance_of(&obj, &ctor).unwrap());
+    assert_eq!(ctx.get_class_opaque::<Counter>(&obj).unwrap().0, 7);
+
+    let plain = ctx.new_object_class(Counter(1), None).unwrap();
+    assert!(!ctx.instance_of(&plain, &ctor).unwrap());
+}
+
+#[test]
+fn test_construct_instance() {
+    struct Thing {
+        size: i32,
+    }
+
+    impl Class for Thing {
+        const NAME: &'static str = "Thing";
+    }
+
+    let rt = Runtime::new();
+    let ctx = rt.new_context();
+
+    let ctor = ctx
+        .new_object_class(
+            NativeFunction::new(|ctx, _, _, args, options| {
+                let size = ctx.to_int32(args.first().unwrap_or(&Value::Undefined))?;
+                let proto = ctx.get_property_str(options.new_target.unwrap(), "prototype")?;
+
+                ctx.new_object_class(Thing { size }, Some(&proto))
+            }),
+            None,
+        )
+        .unwrap();
+    ctx.set_constructor_bit(&ctor, true);
+    ctx.set_property_str(&ctor, "prototype", ctx.new_object(None).unwrap())
+        .unwrap();
+
+    let (obj, thing) = ctx.construct_instance::<Thing>(&ctor, &[Value::Int32(3)]).unwrap();
+    assert_eq!(thing.size, 3);
+    assert!(ctx.instance_of(&obj, &ctor).unwrap());
+
+    let plain = ctx
+        .eval_global(None, "(class Plain {})", "test.js", EvalFlags::STRICT)
+        .unwrap();
+    let Err(err) = ctx.construct_instance::<Thing>(&plain, &[]) else {
+        panic!("plain class constructed a Thing");
+    };
+    let name = ctx.get_property_str(&err, "name").unwrap();
+    assert_eq!(&*ctx.get_string(&name).unwrap(), "TypeError");
+}
@@ -1,4 +1,13 @@
-use libquickjs::{EvalFlags, Runtime, Value};
+use std::{
+    num::NonZeroUsize,
+    sync::{
+        Arc,
+        atomic::{AtomicUsize, Ordering},
+    },
+    time::Duration,
+};
+
+use libquickjs::{EvalError, EvalFlags, EvalTimeout, Intrinsics, NativeFunction, Runtime, Value};
 
 #[test]
 fn test_return_int() {
@@ -30,3 +39,279 @@ fn test_return_string() {
     let s = ctx.get_string(&ret).unwrap();
     assert_eq!(&*s, "114514");
 }
+
+#[test]
+fn test_eval_checked() {
+    let rt = Runtime::new();
+    let ctx = rt.new_context();
+
+    let ret = ctx.eval_checked("1 + 1", "script.js", EvalFlags::empty()).unwrap();
+    assert_eq!(ret, Value::Int32(2));
+
+    match ctx.eval_checked("let = ;", "script.js", EvalFlags::empty()) {
+        Err(EvalError::Compile(err)) => {
+            let name = ctx.get_property_str(&err, "name").unwrap();
+            assert_eq!(&*ctx.get_string(&name).unwrap(), "SyntaxError");
+        }
+        _ => panic!("expecting compile error"),
+    }
+
+    match ctx.eval_checked("throw new RangeError('out of range')", "script.js", EvalFlags::empty()) {
+        Err(EvalError::Runtime(err)) => assert!(ctx.is_error(&err)),
+        _ => panic!("expecting runtime error"),
+    }
+}
+
+#[test]
+fn test_analyze() {
+    let rt = Runtime::new();
+    let ctx = rt.new_context();
+
+    let report = ctx.analyze("let a = 1; a + 1", "clean.js").unwrap();
+    assert!(report.is_clean());
+
+    for code in ["with ({}) {}", "var x = 010;", "function f(a, a) {}", "var eval = 1;"] {
+        let report = ctx.analyze(code, "lint.js").unwrap();
+        let violation = report.violation.unwrap();
+        assert_eq!(violation.kind, "SyntaxError", "{}", code);
+        assert!(!violation.message.is_empty());
+        assert!(violation.stack.unwrap().contains("lint.js"));
+    }
+
+    // analysis never runs the script
+    ctx.analyze("globalThis.ran = true", "side-effect.js").unwrap();
+    assert_eq!(ctx.get_property_str(ctx.global(), "ran").unwrap(), Value::Undefined);
+}
+
+#[test]
+fn test_eval_with_timeout() {
+    let rt = Runtime::new();
+    let ctx = rt.new_context();
+
+    let ret = ctx.eval_global_with_timeout("for (;;) {}", "script.js", EvalFlags::empty(), Duration::from_millis(50));
+    assert_eq!(ret, Err(EvalTimeout::TimedOut));
+
+    let ret = ctx.eval_global_with_timeout("throw 1", "script.js", EvalFlags::empty(), Duration::from_secs(5));
+    assert_eq!(ret, Err(EvalTimeout::Exception(Value::Int32(1))));
+
+    let ret = ctx.eval_global_with_timeout(
+        "let i = 0; while (i < 1000) i++; i",
+        "script.js",
+        EvalFlags::empty(),
+        Duration::from_secs(5),
+    );
+    assert_eq!(ret, Ok(Value::Int32(1000)));
+
+    let ret = ctx.eval_global(None, "for (let i = 0; i < 100000; i++) {} 1", "script.js", EvalFlags::empty());
+    assert_eq!(ret, Ok(Value::Int32(1)));
+}
+
+#[test]
+fn test_nested_eval_with_timeout() {
+    let rt = Runtime::new();
+    let ctx = rt.new_context();
+
+    // timedEval(code, ms) evaluates code under its own deadline
+    let timed_eval = ctx
+        .new_object_class(
+            NativeFunction::new(|ctx, _, _, args, _| {
+                let code = ctx.get_string(&args[0])?.to_string();
+                let Value::Int32(ms) = args[1] else {
+                    return Err(ctx.throw_type_error("expected a timeout"));
+                };
+
+                match ctx.eval_global_with_timeout(code, "inner.js", EvalFlags::empty(), Duration::from_millis(ms as u64)) {
+                    Ok(ret) => Ok(ret),
+                    Err(EvalTimeout::TimedOut) => Err(ctx.new_string("inner timed out")?),
+                    Err(EvalTimeout::Exception(err)) => Err(err),
+                }
+            }),
+            None,
+        )
+        .unwrap();
+    ctx.set_property_str(ctx.global(), "timedEval", timed_eval).unwrap();
+
+    // the outer deadline is back in force once the inner eval returns
+    let ret = ctx.eval_global_with_timeout(
+        "timedEval('1', 5000); for (;;) {}",
+        "script.js",
+        EvalFlags::empty(),
+        Duration::from_millis(50),
+    );
+    assert_eq!(ret, Err(EvalTimeout::TimedOut));
+
+    // and it still fires while a longer inner eval runs
+    let ret = ctx.eval_global_with_timeout(
+        "timedEval('for (;;) {}', 5000)",
+        "script.js",
+        EvalFlags::empty(),
+        Duration::from_millis(50),
+    );
+    assert_eq!(ret, Err(EvalTimeout::TimedOut));
+
+    // a shorter inner deadline only stops the inner eval
+    let ret = ctx.eval_global_with_timeout(
+        "let caught; try { timedEval('for (;;) {}', 50) } catch (e) { caught = e } caught",
+        "script.js",
+        EvalFlags::empty(),
+        Duration::from_secs(5),
+    );
+    let ret = ret.unwrap();
+    assert_eq!(&*ctx.get_string(&ret).unwrap(), "inner timed out");
+
+    let ret = ctx.eval_global(None, "for (let i = 0; i < 100000; i++) {} 1", "script.js", EvalFlags::empty());
+    assert_eq!(ret, Ok(Value::Int32(1)));
+}
+
+#[test]
+fn test_full_context_intrinsics() {
+    let rt = Runtime::new();
+
+    let full = rt.new_full_context();
+    let ret = full
+        .eval_global(None, "typeof JSON + typeof Promise", "script.js", EvalFlags::empty())
+        .unwrap();
+    assert_eq!(&*full.get_string(&ret).unwrap(), "objectfunction");
+
+    let plain = rt.new_plain_context();
+    plain.add_intrinsic(Intrinsics::BaseObjects | Intrinsics::Eval);
+    let ret = plain
+        .eval_global(None, "typeof JSON + typeof Promise", "script.js", EvalFlags::empty())
+        .unwrap();
+    assert_eq!(&*plain.get_string(&ret).unwrap(), "undefinedundefined");
+}
+
+#[test]
+fn test_memory_limit() {
+    let rt = Runtime::new();
+    let ctx = rt.new_context();
+
+    rt.set_memory_limit(NonZeroUsize::new(8 * 1024 * 1024));
+
+    let err = ctx
+        .eval_global(
+            None,
+            "const chunks = []; while (true) { chunks.push(new Array(4096).fill(chunks.length)); }",
+            "script.js",
+            EvalFlags::empty(),
+        )
+        .unwrap_err();
+    assert_ne!(err, Value::Undefined);
+
+    rt.set_memory_limit(None);
+    rt.run_gc();
+
+    let ret = ctx.eval_global(None, "1 + 1", "script.js", EvalFlags::empty()).unwrap();
+    assert_eq!(ret, Value::Int32(2));
+}
+
+#[test]
+fn test_gc_threshold() {
+    let rt = Runtime::new();
+    let ctx = rt.new_context();
+
+    rt.set_gc_threshold(64 * 1024);
+    assert_eq!(rt.gc_threshold(), 64 * 1024);
+
+    let ret = ctx
+        .eval_global(
+            None,
+            "let last; for (let i = 0; i < 10000; i++) { last = { i, next: last && last.i }; } last.next",
+            "script.js",
+            EvalFlags::empty(),
+        )
+        .unwrap();
+    assert_eq!(ret, Value::Int32(9998));
+
+    rt.run_gc();
+
+    let ret = ctx.eval_global(None, "last.i", "script.js", EvalFlags::empty()).unwrap();
+    assert_eq!(ret, Value::Int32(9999));
+}
+
+#[test]
+fn test_quickjs_version() {
+    let version = libquickjs::quickjs_version();
+    assert!(version.split('.').all(|part| !part.is_empty()));
+}
+
+#[test]
+fn test_max_stack_size() {
+    let rt = Runtime::new();
+    rt.set_max_stack_size(NonZeroUsize::new(256 * 1024));
+
+    let ctx = rt.new_context();
+    let err = ctx
+        .eval_global(None, "function f() { return f() } f()", "script.js", EvalFlags::empty())
+        .unwrap_err();
+
+    let message = ctx.to_string(&err).unwrap();
+    assert!(ctx.get_string(&message).unwrap().contains("stack"));
+}
+
+#[test]
+fn test_interrupt_handler() {
+    let polls = Arc::new(AtomicUsize::new(0));
+
+    let rt = Runtime::new();
+    rt.set_interrupt_handler({
+        let polls = polls.clone();
+        move || polls.fetch_add(1, Ordering::SeqCst) >= 10
+    });
+
+    {
+        let ctx = rt.new_context();
+
+        let err = ctx
+            .eval_global(
+                None,
+                "try { for (;;) {} } catch (e) { 'caught' }",
+                "script.js",
+                EvalFlags::empty(),
+            )
+            .unwrap_err();
+        assert!(ctx.is_uncatchable_error(&err));
+        assert!(polls.load(Ordering::SeqCst) > 10);
+
+        // a timed evaluation restores the handler afterwards
+        let ret = ctx.eval_global_with_timeout("1", "script.js", EvalFlags::empty(), Duration::from_secs(5));
+        assert_eq!(ret, Ok(Value::Int32(1)));
+        assert!(ctx.eval_global(None, "for (;;) {}", "script.js", EvalFlags::empty()).is_err());
+
+        rt.clear_interrupt_handler();
+        let ret = ctx.eval_global(
+            None,
+            "for (let i = 0; i < 1000000; i++) {} 1",
+            "script.js",
+            EvalFlags::empty(),
+        );
+        assert_eq!(ret, Ok(Value::Int32(1)));
+    }
+
+    rt.set_interrupt_handler({
+        let polls = polls.clone();
+        move || polls.load(Ordering::SeqCst) == 0
+    });
+    assert_eq!(Arc::strong_count(&polls), 2);
+
+    drop(rt);
+    assert_eq!(Arc::strong_count(&polls), 1);
+}
+
+#[test]
+fn test_compute_memory_usage() {
+    let rt = Runtime::new();
+    let ctx = rt.new_context();
+
+    let before = rt.compute_memory_usage();
+
+    let array = ctx
+        .eval_global(None, "new Array(100000).fill(1.5)", "script.js", EvalFlags::empty())
+        .unwrap();
+
+    let after = rt.compute_memory_usage();
+    assert!(after.memory_used_size > before.memory_used_size);
+    assert!(after.array_count > before.array_count);
+
+    drop(array);
+}
@@ -2,7 +2,10 @@
 
 use std::collections::HashMap;
 
-use libquickjs::{EvalFlags, Runtime, serde::from_value};
+use libquickjs::{
+    EvalFlags, Runtime, Value,
+    serde::{ErrorRepr, JsDate, JsValue, from_value},
+};
 use serde::Deserialize;
 
 #[test]
@@ -104,6 +107,31 @@ fn test_deserialize_arrays() {
     assert_eq!(mixed_result[4], MixedValue::Array(vec![1, 2]));
 }
 
+#[test]
+fn test_deserialize_proxied_array() {
+    let rt = Runtime::new();
+    let ctx = rt.new_context();
+
+    let proxy = ctx
+        .eval_global(None, "new Proxy([1, 2], {})", "test.js", EvalFlags::STRICT)
+        .unwrap();
+
+    let seq: Vec<i32> = from_value(&ctx, &proxy).unwrap();
+    assert_eq!(seq, vec![1, 2]);
+
+    let tuple: (i32, i32) = from_value(&ctx, &proxy).unwrap();
+    assert_eq!(tuple, (1, 2));
+
+    #[derive(Deserialize, Debug, PartialEq)]
+    #[serde(untagged)]
+    enum AnyValue {
+        Array(Vec<i32>),
+        Map(HashMap<String, i32>),
+    }
+    let any: AnyValue = from_value(&ctx, &proxy).unwrap();
+    assert_eq!(any, AnyValue::Array(vec![1, 2]));
+}
+
 #[test]
 fn test_deserialize_enums() {
     let rt = Runtime::new();
@@ -143,6 +171,13 @@ fn test_deserialize_tuples() {
         .unwrap();
     let nested_tuple_result: Vec<(i32, i32)> = from_value(&ctx, &nested_tuple_val).unwrap();
     assert_eq!(nested_tuple_result, vec![(1, 2), (3, 4)]);
+
+    // Test length mismatch
+    for code in ["([1, 2])", "([1, 2, 3, 4])"] {
+        let value = ctx.eval_global(None, code, "test.js", EvalFlags::STRICT).unwrap();
+        let err = from_value::<(f64, f64, f64)>(&ctx, &value).unwrap_err();
+        assert!(matches!(err.repr(), ErrorRepr::TupleLengthMismatch { expected: 3, .. }));
+    }
 }
 
 #[test]
@@ -277,3 +312,58 @@ fn test_deserialize_map() {
     assert_eq!(seq_map_result.get(1), Some(&("b".to_string(), 2)));
     assert_eq!(seq_map_result.get(2), Some(&("c".to_string(), 3)));
 }
+
+#[test]
+fn test_deserialize_js_value() {
+    let rt = Runtime::new();
+    let ctx = rt.new_context();
+
+    let obj = ctx
+        .eval_global(
+            None,
+            r#"({ name: "add", callback: (a, b) => a + b })"#,
+            "test.js",
+            EvalFlags::STRICT,
+        )
+        .unwrap();
+
+    #[derive(Deserialize)]
+    struct Handler {
+        name: String,
+        callback: JsValue,
+    }
+
+    let handler: Handler = from_value(&ctx, &obj).unwrap();
+    assert_eq!(handler.name, "add");
+
+    let callback = handler.callback.0.to_local(&rt).unwrap();
+    let ret = ctx
+        .call(&callback, &Value::Undefined, &[Value::Int32(1), Value::Int32(2)])
+        .unwrap();
+    assert_eq!(ret, Value::Int32(3));
+}
+
+#[test]
+fn test_deserialize_date() {
+    let rt = Runtime::new();
+    let ctx = rt.new_context();
+
+    let obj = ctx
+        .eval_global(
+            None,
+            r#"({ at: new Date(86400000), raw: 1000 })"#,
+            "test.js",
+            EvalFlags::STRICT,
+        )
+        .unwrap();
+
+    #[derive(Deserialize)]
+    struct Event {
+        at: JsDate,
+        raw: JsDate,
+    }
+
+    let event: Event = from_value(&ctx, &obj).unwrap();
+    assert_eq!(event.at, JsDate(86_400_000.0));
+    assert_eq!(event.raw, JsDate(1000.0));
+}
@@ -1,7 +1,12 @@
 #![cfg(feature = "serde")]
 
-use libquickjs::{EvalFlags, Runtime, Value, serde::to_value};
-use serde::{Serialize, Serializer};
+use std::collections::HashMap;
+
+use libquickjs::{
+    EvalFlags, Runtime, Value,
+    serde::{JsDate, from_value, to_value, to_value_ordered},
+};
+use serde::{Serialize, Serializer, ser::SerializeMap};
 
 #[test]
 fn test_serialize_object() {
@@ -323,3 +328,73 @@ fn test_serialize_bytes() {
         }
     }
 }
+
+#[test]
+fn test_serialize_date() {
+    let rt = Runtime::new();
+    let ctx = rt.new_context();
+
+    #[derive(Serialize)]
+    struct Event {
+        at: JsDate,
+    }
+
+    let value = to_value(
+        &ctx,
+        &Event {
+            at: JsDate(86_400_000.0),
+        },
+    )
+    .unwrap();
+    let at = ctx.get_property_str(&value, "at").unwrap();
+    assert!(ctx.is_date(&at));
+
+    let global_obj = ctx.get_global_object();
+    ctx.set_property_str(&global_obj, "at", at).unwrap();
+    let iso = ctx
+        .eval_global(None, "at.toISOString()", "test.js", EvalFlags::STRICT)
+        .unwrap();
+    assert_eq!(&*ctx.get_string(&iso).unwrap(), "1970-01-02T00:00:00.000Z");
+}
+
+#[test]
+fn test_serialize_map_order() {
+    struct Ordered(Vec<(&'static str, i32)>);
+
+    impl Serialize for Ordered {
+        fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+            let mut map = serializer.serialize_map(Some(self.0.len()))?;
+            for (k, v) in &self.0 {
+                map.serialize_entry(k, v)?;
+            }
+            map.end()
+        }
+    }
+
+    let rt = Runtime::new();
+    let ctx = rt.new_context();
+
+    let entries = Ordered(vec![("b", 1), ("10", 2), ("2", 3)]);
+    let keys = ctx
+        .eval_global(
+            None,
+            "(v) => Array.from(v instanceof Map ? v.keys() : Object.keys(v)).join()",
+            "test.js",
+            EvalFlags::STRICT,
+        )
+        .unwrap();
+
+    // plain objects follow JS property order, integer-like keys first
+    let object = to_value(&ctx, &entries).unwrap();
+    let ret = ctx.call(&keys, &Value::Undefined, &[object]).unwrap();
+    assert_eq!(&*ctx.get_string(&ret).unwrap(), "2,10,b");
+
+    let map = to_value_ordered(&ctx, &entries).unwrap();
+    assert!(ctx.is_map(&map));
+    let ret = ctx.call(&keys, &Value::Undefined, &[map.clone()]).unwrap();
+    assert_eq!(&*ctx.get_string(&ret).unwrap(), "b,10,2");
+
+    let back: HashMap<String, i32> = from_value(&ctx, &map).unwrap();
+    assert_eq!(back.len(), 3);
+    assert_eq!(back["10"], 2);
+}
@@ -1,4 +1,4 @@
-use libquickjs::{ReadObjectFlags, Runtime, WriteObjectFlags};
+use libquickjs::{EvalFlags, PromiseState, ReadObjectFlags, Runtime, Value, WriteObjectFlags};
 
 #[test]
 fn test_write_read_object() {
@@ -17,3 +17,97 @@ fn test_write_read_object() {
     let foo_str = ctx.get_string(&foo).unwrap();
     assert_eq!(&*foo_str, "bar");
 }
+
+#[test]
+fn test_child_realm_transfer() {
+    let rt = Runtime::new();
+    let ctx = rt.new_context();
+
+    ctx.set_property_str(&ctx.get_global_object(), "secret", ctx.new_string("parent").unwrap().into())
+        .unwrap();
+
+    let child = ctx.spawn_child_realm();
+    let secret = child.get_property_str(&child.get_global_object(), "secret").unwrap();
+    assert!(matches!(secret, libquickjs::Value::Undefined));
+
+    let obj = ctx.new_object(None).unwrap();
+    ctx.set_property_str(&obj, "foo", ctx.new_string("bar").unwrap().into())
+        .unwrap();
+
+    let moved = ctx.transfer_value(&obj, &child).unwrap();
+    let foo = child.get_property_str(&moved, "foo").unwrap();
+    assert_eq!(&*child.get_string(&foo).unwrap(), "bar");
+}
+
+#[test]
+fn test_bytecode_kind() {
+    let rt = Runtime::new();
+    let ctx = rt.new_context();
+
+    let func = ctx.eval_global(None, "40 + 2", "script.js", EvalFlags::COMPILE_ONLY).unwrap();
+    assert!(func.is_function_bytecode());
+    assert!(!func.is_module());
+
+    let data = ctx.write_object(&func, WriteObjectFlags::BYTECODE).unwrap();
+    let func = ctx.read_object(&data, ReadObjectFlags::BYTECODE).unwrap();
+    assert!(func.is_function_bytecode());
+    assert_eq!(ctx.eval_function(func).unwrap(), Value::Int32(42));
+
+    let module = ctx
+        .eval_module("export const a = 1;", "module.js", EvalFlags::COMPILE_ONLY)
+        .unwrap();
+    assert!(module.is_module());
+    assert!(!module.is_function_bytecode());
+}
+
+#[test]
+fn test_link_and_evaluate_module() {
+    let rt = Runtime::new();
+    let ctx = rt.new_context();
+
+    rt.set_module_loader(|ctx, name| match name {
+        "dep" => ctx.eval_module("export const base = 40;", name, EvalFlags::COMPILE_ONLY),
+        _ => Err(ctx.new_string(format!("no such module: {}", name)).unwrap()),
+    });
+
+    let module = ctx
+        .eval_module(
+            "import { base } from 'dep'; globalThis.answer = base + 2;",
+            "plugin.js",
+            EvalFlags::COMPILE_ONLY,
+        )
+        .unwrap();
+    let data = ctx.write_object(&module, WriteObjectFlags::BYTECODE).unwrap();
+
+    let module = ctx.read_object(&data, ReadObjectFlags::BYTECODE).unwrap();
+    let promise = ctx.link_and_evaluate_module(module).unwrap();
+    rt.execute_pending_jobs();
+    assert_eq!(ctx.get_promise_state(&promise).unwrap(), PromiseState::Fulfilled);
+
+    let answer = ctx.get_property_str(&ctx.get_global_object(), "answer").unwrap();
+    assert_eq!(answer, Value::Int32(42));
+
+    assert!(ctx.link_and_evaluate_module(Value::Int32(1)).is_err());
+}
+
+#[test]
+fn test_compile() {
+    let rt = Runtime::new();
+    let ctx = rt.new_context();
+
+    let func = ctx.compile("1+1", "script.js", EvalFlags::empty()).unwrap();
+    assert!(func.is_function_bytecode());
+
+    let data = ctx.write_object(&func, WriteObjectFlags::BYTECODE).unwrap();
+    let func = ctx.read_object(&data, ReadObjectFlags::BYTECODE).unwrap();
+    for _ in 0..3 {
+        assert_eq!(ctx.eval_function(func.clone()).unwrap(), Value::Int32(2));
+    }
+
+    let module = ctx
+        .compile_module("globalThis.loaded = true;", "module.js", EvalFlags::empty())
+        .unwrap();
+    assert!(module.is_module());
+    ctx.eval_function_async(module).unwrap();
+    assert_eq!(ctx.get_property_str(ctx.global(), "loaded").unwrap(), Value::Bool(true));
+}
@@ -0,0 +1,151 @@
+use libquickjs::{EvalFlags, Runtime, Value};
+
+#[test]
+fn test_new_string_utf16() {
+    let rt = Runtime::new();
+    let ctx = rt.new_context();
+
+    let units: Vec<u16> = "a\u{e9}\u{4e2d}\u{1f600}"
+        .encode_utf16()
+        .chain([0xD800, 0x62, 0xDC00])
+        .collect();
+    let s = ctx.new_string_utf16(&units).unwrap();
+
+    let global_obj = ctx.get_global_object();
+    ctx.set_property_str(&global_obj, "s", s).unwrap();
+
+    let codes = ctx
+        .eval_global(
+            None,
+            "Array.from({ length: s.length }, (_, i) => s.charCodeAt(i)).join(',')",
+            "test.js",
+            EvalFlags::STRICT,
+        )
+        .unwrap();
+    let expected = units.iter().map(|u| u.to_string()).collect::<Vec<_>>().join(",");
+    assert_eq!(&*ctx.get_string(&codes).unwrap(), expected);
+
+    let ascii = ctx.new_string_utf16(&"hello".encode_utf16().collect::<Vec<_>>()).unwrap();
+    assert_eq!(&*ctx.get_string(&ascii).unwrap(), "hello");
+
+    let empty = ctx.new_string_utf16(&[]).unwrap();
+    assert!(matches!(empty, Value::String(_)));
+}
+
+#[test]
+fn test_symbol_description() {
+    let rt = Runtime::new();
+    let ctx = rt.new_context();
+
+    let sym = ctx.new_symbol("my.symbol", false).unwrap();
+    assert_eq!(ctx.symbol_description(&sym).unwrap().as_deref(), Some("my.symbol"));
+
+    let anonymous = ctx.eval_global(None, "Symbol()", "test.js", EvalFlags::STRICT).unwrap();
+    assert_eq!(ctx.symbol_description(&anonymous).unwrap(), None);
+
+    assert!(ctx.symbol_description(&Value::Int32(1)).is_err());
+}
+
+#[test]
+fn test_describe() {
+    let rt = Runtime::new();
+    let ctx = rt.new_context();
+
+    let describe = |code: &str| ctx.describe(&ctx.eval_global(None, code, "test.js", EvalFlags::STRICT).unwrap());
+
+    assert_eq!(describe("42"), "42");
+    assert_eq!(describe("'text'"), "text");
+    assert_eq!(describe("undefined"), "undefined");
+    assert_eq!(describe("[1, 2]"), "1,2");
+    assert_eq!(describe("Symbol('tag')"), "Symbol(tag)");
+    assert_eq!(describe("Symbol()"), "Symbol()");
+    assert_eq!(describe("Object.create(null)"), "[object Object]");
+    assert_eq!(describe("({ toString() { throw new Error('no') } })"), "[object Object]");
+}
+
+#[test]
+fn test_debug_redaction() {
+    let rt = Runtime::new();
+    let ctx = rt.new_context();
+
+    let secret = ctx.new_string("hunter2").unwrap();
+    assert!(format!("{:?}", secret).contains("hunter2"));
+
+    rt.set_debug_redaction(true);
+    assert!(!format!("{:?}", secret).contains("hunter2"));
+
+    rt.set_debug_redaction(false);
+    assert!(format!("{:?}", secret).contains("hunter2"));
+}
+
+#[test]
+fn test_debug_reuses_context() {
+    let rt = Runtime::new();
+    let ctx = rt.new_context();
+
+    let values = (0..100)
+        .map(|i| ctx.new_string(format!("value {}", i)).unwrap())
+        .collect::<Vec<_>>();
+    let symbol = ctx.new_symbol("tag", false).unwrap();
+
+    assert!(format!("{:?}", values[0]).contains("value 0"));
+    let before = rt.compute_memory_usage();
+
+    for _ in 0..100 {
+        for (i, value) in values.iter().enumerate() {
+            assert!(format!("{:?}", value).contains(&format!("value {}", i)));
+        }
+
+        // symbols fail to stringify, which must not leak a pending exception
+        let _ = format!("{:?}", symbol);
+    }
+
+    let after = rt.compute_memory_usage();
+    assert_eq!(before.memory_used_size, after.memory_used_size);
+    assert_eq!(before.obj_count, after.obj_count);
+}
+
+#[test]
+fn test_get_string_lossy() {
+    let rt = Runtime::new();
+    let ctx = rt.new_context();
+
+    let lone = ctx
+        .eval_global(None, "'a' + String.fromCharCode(0xD800) + 'b'", "test.js", EvalFlags::STRICT)
+        .unwrap();
+
+    let s = ctx.get_string(&lone).unwrap();
+    assert!(s.to_str().is_err());
+    assert_eq!(s.as_bytes(), b"a\xed\xa0\x80b");
+    assert_eq!(&*s, "a\u{FFFD}\u{FFFD}\u{FFFD}b");
+    assert_eq!(ctx.get_string_lossy(&lone).unwrap(), "a\u{FFFD}\u{FFFD}\u{FFFD}b");
+
+    let valid = ctx.new_string("héllo").unwrap();
+    assert_eq!(ctx.get_string(&valid).unwrap().to_str(), Ok("héllo"));
+    assert_eq!(ctx.get_string_lossy(&valid).unwrap(), "héllo");
+}
+
+#[test]
+fn test_lone_surrogate_description() {
+    let rt = Runtime::new();
+    let ctx = rt.new_context();
+
+    let symbol = ctx
+        .eval_global(None, "Symbol(String.fromCharCode(0xD800))", "test.js", EvalFlags::STRICT)
+        .unwrap();
+    assert_eq!(
+        ctx.symbol_description(&symbol).unwrap().as_deref(),
+        Some("\u{FFFD}\u{FFFD}\u{FFFD}")
+    );
+    assert_eq!(ctx.describe(&symbol), "Symbol(\u{FFFD}\u{FFFD}\u{FFFD})");
+
+    let lone = ctx
+        .eval_global(None, "String.fromCharCode(0xD800)", "test.js", EvalFlags::STRICT)
+        .unwrap();
+    assert_eq!(ctx.describe(&lone), "\u{FFFD}\u{FFFD}\u{FFFD}");
+    assert_eq!(format!("{:?}", lone).matches('\u{FFFD}').count(), 3);
+
+    let mut out = Vec::new();
+    ctx.stringify_to_writer(&lone, &mut out).unwrap();
+    assert_eq!(out, b"\"\\ud800\"");
+}
@@ -0,0 +1,128 @@
+use std::collections::HashMap;
+
+use libquickjs::{EvalFlags, InvalidValueType, JsKind, Runtime, Value};
+
+#[test]
+fn test_try_from_value() {
+    let rt = Runtime::new();
+    let ctx = rt.new_context();
+
+    let int = ctx.eval_global(None, "40 + 2", "test.js", EvalFlags::STRICT).unwrap();
+    assert_eq!(i32::try_from(&int), Ok(42));
+    assert_eq!(f64::try_from(&int), Ok(42.0));
+    assert_eq!(bool::try_from(&int), Err(InvalidValueType));
+
+    let float = ctx.eval_global(None, "1.5", "test.js", EvalFlags::STRICT).unwrap();
+    assert_eq!(f64::try_from(&float), Ok(1.5));
+    assert_eq!(i32::try_from(&float), Err(InvalidValueType));
+
+    assert_eq!(bool::try_from(&Value::Bool(true)), Ok(true));
+    assert_eq!(i32::try_from(&Value::Undefined), Err(InvalidValueType));
+
+    let s = ctx.new_string("1").unwrap();
+    assert_eq!(i32::try_from(&s), Err(InvalidValueType));
+}
+
+#[test]
+fn test_number_to_string_radix() {
+    let rt = Runtime::new();
+    let ctx = rt.new_context();
+
+    assert_eq!(ctx.number_to_string_radix(&Value::Int32(255), 16).unwrap(), "ff");
+    assert_eq!(ctx.number_to_string_radix(&Value::Int32(-5), 2).unwrap(), "-101");
+    assert_eq!(ctx.number_to_string_radix(&Value::Float64(0.5), 2).unwrap(), "0.1");
+    assert_eq!(ctx.number_to_string_radix(&Value::Float64(-0.0), 10).unwrap(), "0");
+
+    assert!(ctx.number_to_string_radix(&Value::Int32(1), 1).is_err());
+    assert!(ctx.number_to_string_radix(&ctx.new_string("1").unwrap(), 10).is_err());
+}
+
+#[test]
+fn test_object_identity_key() {
+    let rt = Runtime::new();
+    let ctx = rt.new_context();
+
+    let Value::Object(a) = ctx.new_object(None).unwrap() else {
+        unreachable!()
+    };
+    let Value::Object(b) = ctx.new_object(None).unwrap() else {
+        unreachable!()
+    };
+
+    let mut side = HashMap::new();
+    side.insert(a.clone(), "a");
+    side.insert(b.clone(), "b");
+    side.insert(a.clone(), "a2");
+
+    assert_eq!(side.len(), 2);
+    assert_eq!(side.get(&a), Some(&"a2"));
+    assert_eq!(side.get(&b), Some(&"b"));
+}
+
+#[test]
+fn test_to_uint32_length() {
+    let rt = Runtime::new();
+    let ctx = rt.new_context();
+
+    let eval = |code: &str| ctx.eval_global(None, code, "test.js", EvalFlags::STRICT).unwrap();
+
+    assert_eq!(ctx.to_uint32(&eval("-1")).unwrap(), u32::MAX);
+    assert_eq!(ctx.to_uint32(&eval("2 ** 32 + 5")).unwrap(), 5);
+    assert_eq!(ctx.to_uint32(&eval("'7.9'")).unwrap(), 7);
+    assert_eq!(ctx.to_uint32(&eval("NaN")).unwrap(), 0);
+
+    assert_eq!(ctx.to_length(&eval("-10")).unwrap(), 0);
+    assert_eq!(ctx.to_length(&eval("NaN")).unwrap(), 0);
+    assert_eq!(ctx.to_length(&eval("'12.7'")).unwrap(), 12);
+    assert_eq!(ctx.to_length(&eval("Infinity")).unwrap(), (1 << 53) - 1);
+
+    assert!(ctx.to_length(&eval("Symbol()")).is_err());
+}
+
+#[test]
+fn test_classify() {
+    let rt = Runtime::new();
+    let ctx = rt.new_context();
+
+    let kind = |code: &str| ctx.classify(&ctx.eval_global(None, code, "test.js", EvalFlags::STRICT).unwrap());
+
+    assert_eq!(kind("undefined"), JsKind::Undefined);
+    assert_eq!(kind("null"), JsKind::Null);
+    assert_eq!(kind("true"), JsKind::Bool);
+    assert_eq!(kind("1.5"), JsKind::Number);
+    assert_eq!(kind("10n"), JsKind::BigInt);
+    assert_eq!(kind("'s'"), JsKind::String);
+    assert_eq!(kind("Symbol()"), JsKind::Symbol);
+    assert_eq!(kind("[1, 2]"), JsKind::Array);
+    assert_eq!(kind("new Uint8Array(2)"), JsKind::TypedArray);
+    assert_eq!(kind("() => 1"), JsKind::Function);
+    assert_eq!(kind("Math.max"), JsKind::Function);
+    assert_eq!(kind("(function () {}).bind(null)"), JsKind::Function);
+    assert_eq!(kind("(async () => {})"), JsKind::Function);
+    assert_eq!(kind("new Proxy(() => {}, {})"), JsKind::Function);
+    assert_eq!(kind("({})"), JsKind::Object);
+    assert_eq!(kind("new Date(0)"), JsKind::Object);
+    assert_eq!(kind("new Proxy([], {})"), JsKind::Array);
+    assert_eq!(kind("new Proxy(new Proxy([], {}), {})"), JsKind::Array);
+    assert_eq!(kind("new Proxy({}, {})"), JsKind::Object);
+    assert_eq!(
+        kind("(() => { const p = Proxy.revocable([], {}); p.revoke(); return p.proxy })()"),
+        JsKind::Object
+    );
+}
+
+#[test]
+fn test_dup_raw() {
+    let rt = Runtime::new();
+    let ctx = rt.new_context();
+
+    let obj = ctx.new_object(None).unwrap();
+    ctx.set_property_str(&obj, "answer", Value::Int32(42)).unwrap();
+
+    let dup = unsafe { rt.dup_raw(obj.as_raw()) };
+    assert_eq!(dup, obj);
+    drop(obj);
+
+    assert_eq!(ctx.get_property_str(&dup, "answer").unwrap(), Value::Int32(42));
+    assert_eq!(unsafe { rt.dup_raw(Value::Int32(7).as_raw()) }, Value::Int32(7));
+}
@@ -1,6 +1,6 @@
 use std::sync::{Arc, atomic::AtomicBool};
 
-use libquickjs::{Class, Runtime};
+use libquickjs::{Class, Runtime, Value};
 
 #[test]
 fn test_weak_ref() {
@@ -48,3 +48,25 @@ fn test_weak_ref() {
 
     assert_eq!(closed.load(std::sync::atomic::Ordering::Relaxed), true);
 }
+
+#[test]
+fn test_weak_map_set() {
+    let rt = Runtime::new();
+    let ctx = rt.new_context();
+
+    let map = ctx.new_weak_map().unwrap();
+    let key = ctx.new_object(None).unwrap();
+    let other = ctx.new_object(None).unwrap();
+
+    ctx.weak_map_set(&map, &key, Value::Int32(7)).unwrap();
+    assert!(ctx.weak_map_has(&map, &key).unwrap());
+    assert!(!ctx.weak_map_has(&map, &other).unwrap());
+    assert_eq!(ctx.weak_map_get(&map, &key).unwrap(), Value::Int32(7));
+    assert_eq!(ctx.weak_map_get(&map, &other).unwrap(), Value::Undefined);
+    assert!(ctx.weak_map_set(&map, &Value::Int32(1), Value::Null).is_err());
+
+    let set = ctx.new_weak_set().unwrap();
+    ctx.weak_set_add(&set, &key).unwrap();
+    assert!(ctx.weak_set_has(&set, &key).unwrap());
+    assert!(!ctx.weak_set_has(&set, &other).unwrap());
+}